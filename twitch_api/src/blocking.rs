@@ -0,0 +1,736 @@
+//! A blocking Helix client
+//!
+//! Wraps the asynchronous [`HelixClient`](crate::HelixClient) and performs
+//! requests on a runtime owned by the client. Requests are built the same way
+//! and are sent with `send`. Options specific to an endpoint are available on
+//! the blocking builders as well. Anything else can be applied to the
+//! underlying asynchronous builder with `map`.
+//!
+//! ```no_run
+//! use twitch_api::blocking::HelixClient;
+//!
+//! let client = HelixClient::new("client_id")?
+//!     .authenticate("secret")
+//!     .build()?;
+//! let streams = client.streams().get().first(100).send()?;
+//! # Ok::<(), twitch_api::error::Error>(())
+//! ```
+//!
+//! The blocking client must not be used from within an asynchronous runtime.
+
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use hyper::body::Bytes;
+use hyper::Request;
+use serde::de::DeserializeOwned;
+use tokio::runtime::Runtime;
+use twitch_types::{BroadcasterId, ClipId, GameId, UserId, VideoId};
+
+use crate::client::{
+    BidirectionalPagination, DefaultOpts, ForwardPagination, HelixPagination, HelixScope,
+    IterableApiRequest, PaginationContrainerTrait, RequestBuilder as AsyncRequestBuilder,
+};
+use crate::error::{Error, ValidationError};
+use crate::helix::models::{
+    ApiError, BitsLeaderboard, BroadcasterSubscriptions, Channel, Cheermote, Clip,
+    ContentClassificationLabel, CreatedClip, DataContainer, ExtensionTransaction, FollowedChannel,
+    Follower, Game, PaginationContainer, SearchChannel, Stream, User, UserMarkers, Video,
+};
+use crate::helix::namespaces::bits::{Bits, LeaderboardPeriod};
+use crate::helix::namespaces::channels::{ChannelUpdate, Channels, Follows};
+use crate::helix::namespaces::clips::Clips;
+use crate::helix::namespaces::games::Games;
+use crate::helix::namespaces::search::Search;
+use crate::helix::namespaces::streams::{MarkerCreate, Markers, Streams};
+use crate::helix::namespaces::subscriptions::{SubscriptionCheck, Subscriptions};
+use crate::helix::namespaces::users::Users;
+use crate::helix::namespaces::videos::Videos;
+use crate::helix::namespaces::Namespace as AsyncNamespace;
+use crate::helix::token_store::TokenStore;
+use crate::helix::AuthClientBuilder as AsyncAuthClientBuilder;
+use crate::helix::Client as AsyncClient;
+use crate::ClientConfig;
+
+#[derive(Clone, Debug)]
+pub struct HelixClient {
+    inner: AsyncClient,
+    runtime: Arc<Runtime>,
+}
+
+fn runtime() -> Result<Runtime, Error> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    Ok(runtime)
+}
+
+impl HelixClient {
+    pub fn new(id: &str) -> Result<HelixClient, Error> {
+        Ok(HelixClient {
+            runtime: Arc::new(runtime()?),
+            inner: AsyncClient::new(id),
+        })
+    }
+
+    pub fn new_with_config<S: Into<String>>(
+        id: S,
+        config: ClientConfig,
+    ) -> Result<HelixClient, Error> {
+        Ok(HelixClient {
+            runtime: Arc::new(runtime()?),
+            inner: AsyncClient::new_with_config(id, config),
+        })
+    }
+
+    pub fn authenticate<S: Into<String>>(self, secret: S) -> AuthClientBuilder {
+        AuthClientBuilder {
+            inner: self.inner.authenticate(secret),
+            runtime: self.runtime,
+        }
+    }
+
+    /// A handle that performs requests with the user access `token`
+    pub fn as_user(&self, token: &str) -> HelixClient {
+        HelixClient {
+            inner: self.inner.as_user(token),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    pub fn authenticated(&self) -> bool {
+        self.inner.authenticated()
+    }
+
+    pub fn scopes(&self) -> &[HelixScope] {
+        self.inner.scopes()
+    }
+
+    /// The asynchronous client used to perform requests
+    pub fn as_async(&self) -> &AsyncClient {
+        &self.inner
+    }
+
+    /// Fetch the page after `page`. Returns None when there are no further pages
+    pub fn next_page<T>(&self, page: &T) -> Result<Option<T>, Error>
+    where
+        T: BidirectionalPagination<T, ApiError>
+            + DeserializeOwned
+            + PaginationContrainerTrait
+            + Send
+            + 'static,
+    {
+        self.send_page(page.next())
+    }
+
+    /// Fetch the page before `page`. Returns None when there are no previous pages
+    pub fn prev_page<T>(&self, page: &T) -> Result<Option<T>, Error>
+    where
+        T: BidirectionalPagination<T, ApiError>
+            + DeserializeOwned
+            + PaginationContrainerTrait
+            + Send
+            + 'static,
+    {
+        self.send_page(page.prev())
+    }
+
+    fn send_page<T>(
+        &self,
+        request: Option<IterableApiRequest<T, ApiError>>,
+    ) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned + PaginationContrainerTrait + Send + 'static,
+    {
+        match request {
+            Some(request) => self.runtime.block_on(request.into_future()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn namespace<T>(&self) -> Namespace<T> {
+        Namespace {
+            client: self.clone(),
+            _type: PhantomData,
+        }
+    }
+
+    fn wrap<T, E, Opt>(&self, inner: AsyncRequestBuilder<T, E, Opt>) -> RequestBuilder<T, E, Opt> {
+        RequestBuilder {
+            inner,
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    fn wrap_builder<B>(&self, inner: B) -> Builder<B> {
+        Builder {
+            inner,
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+pub struct AuthClientBuilder {
+    inner: AsyncAuthClientBuilder,
+    runtime: Arc<Runtime>,
+}
+
+impl AuthClientBuilder {
+    pub fn build(self) -> Result<HelixClient, Error> {
+        let inner = self.runtime.block_on(self.inner.build())?;
+        Ok(HelixClient {
+            inner,
+            runtime: self.runtime,
+        })
+    }
+
+    pub fn scope(self, scope: HelixScope) -> AuthClientBuilder {
+        AuthClientBuilder {
+            inner: self.inner.scope(scope),
+            runtime: self.runtime,
+        }
+    }
+
+    pub fn scopes(self, scopes: Vec<HelixScope>) -> AuthClientBuilder {
+        AuthClientBuilder {
+            inner: self.inner.scopes(scopes),
+            runtime: self.runtime,
+        }
+    }
+
+    pub fn token(self, token: &str) -> AuthClientBuilder {
+        AuthClientBuilder {
+            inner: self.inner.token(token),
+            runtime: self.runtime,
+        }
+    }
+
+    pub fn token_store<S: Into<String>>(
+        self,
+        store: Arc<dyn TokenStore>,
+        key: S,
+    ) -> AuthClientBuilder {
+        AuthClientBuilder {
+            inner: self.inner.token_store(store, key),
+            runtime: self.runtime,
+        }
+    }
+}
+
+/// Blocking wrapper of an asynchronous builder of its own, such as
+/// `MarkerCreate`
+pub struct Builder<B> {
+    inner: B,
+    runtime: Arc<Runtime>,
+}
+
+impl<B: IntoFuture> Builder<B> {
+    /// Apply options of the underlying asynchronous builder
+    pub fn map<F: FnOnce(B) -> B>(self, f: F) -> Self {
+        Builder {
+            inner: f(self.inner),
+            runtime: self.runtime,
+        }
+    }
+
+    /// The underlying asynchronous builder, e.g. to validate it
+    pub fn as_async(&self) -> &B {
+        &self.inner
+    }
+
+    pub fn send(self) -> B::Output {
+        self.runtime.block_on(self.inner.into_future())
+    }
+}
+
+pub struct RequestBuilder<T, E = ApiError, Opts = DefaultOpts> {
+    inner: AsyncRequestBuilder<T, E, Opts>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T, E, Opt> RequestBuilder<T, E, Opt> {
+    pub fn with_query<S: Into<String>, S2: Into<String>>(self, key: S, value: S2) -> Self {
+        self.map(|b| b.with_query(key, value))
+    }
+
+    pub fn coalesce(self, coalesce: bool) -> Self {
+        self.map(|b| b.coalesce(coalesce))
+    }
+
+    pub fn as_user(self, token: &str) -> Self {
+        self.map(|b| b.as_user(token))
+    }
+
+    /// Apply options of the underlying asynchronous request builder
+    pub fn map<F>(self, f: F) -> Self
+    where
+        F: FnOnce(AsyncRequestBuilder<T, E, Opt>) -> AsyncRequestBuilder<T, E, Opt>,
+    {
+        RequestBuilder {
+            inner: f(self.inner),
+            runtime: self.runtime,
+        }
+    }
+}
+
+impl<T, E, Opt> RequestBuilder<T, E, Opt>
+where
+    T: DeserializeOwned + ForwardPagination + 'static + Send,
+    E: DeserializeOwned + 'static + Send,
+{
+    pub fn send(self) -> Result<T, Error> {
+        self.runtime.block_on(self.inner.into_future())
+    }
+
+    /// The http request `send` would perform. See the asynchronous
+    /// `RequestBuilder::prepare`
+    pub fn prepare(self, redact: bool) -> Result<Request<Bytes>, Error> {
+        self.inner.prepare(redact)
+    }
+}
+
+impl<T, E, Opt> RequestBuilder<T, E, Opt>
+where
+    T: DeserializeOwned + ForwardPagination + PaginationContrainerTrait + HelixPagination,
+    T: 'static + Send,
+    E: DeserializeOwned + 'static + Send,
+{
+    /// Send the request for the first page. Further pages are obtained with
+    /// `HelixClient::next_page`
+    pub fn send_iterable(self) -> Result<T, Error> {
+        self.runtime
+            .block_on(self.inner.build_iterable().into_future())
+    }
+}
+
+/* Options of the endpoints, forwarded to the asynchronous builders. See there
+ * for their documentation */
+
+impl<T, E> RequestBuilder<T, E, Streams> {
+    pub fn first(self, first: u32) -> Self {
+        self.map(|b| b.first(first))
+    }
+}
+
+impl<T, E> RequestBuilder<T, E, Markers> {
+    pub fn first(self, first: u32) -> Self {
+        self.map(|b| b.first(first))
+    }
+}
+
+impl<T> RequestBuilder<T, ApiError, Clips> {
+    pub fn started_at(self, start: DateTime<Utc>) -> Self {
+        self.map(|b| b.started_at(start))
+    }
+
+    pub fn ended_at(self, end: DateTime<Utc>) -> Self {
+        self.map(|b| b.ended_at(end))
+    }
+
+    pub fn first(self, first: u32) -> Self {
+        self.map(|b| b.first(first))
+    }
+
+    pub fn is_featured(self, featured: bool) -> Self {
+        self.map(|b| b.is_featured(featured))
+    }
+}
+
+impl<T> RequestBuilder<T, ApiError, Games> {
+    pub fn first(self, first: u32) -> Self {
+        self.map(|b| b.first(first))
+    }
+}
+
+impl<T> RequestBuilder<T, ApiError, Search> {
+    pub fn first(self, first: u32) -> Self {
+        self.map(|b| b.first(first))
+    }
+}
+
+impl RequestBuilder<PaginationContainer<SearchChannel>, ApiError, Search> {
+    pub fn live_only(self, live_only: bool) -> Self {
+        self.map(|b| b.live_only(live_only))
+    }
+}
+
+impl<T, E> RequestBuilder<T, E, Videos> {
+    pub fn language<S: Into<String>>(self, lang: S) -> Self {
+        self.map(|b| b.language(lang))
+    }
+
+    pub fn period<S: Into<String>>(self, period: S) -> Self {
+        self.map(|b| b.period(period))
+    }
+
+    pub fn sort<S: Into<String>>(self, sort: S) -> Self {
+        self.map(|b| b.sort(sort))
+    }
+
+    pub fn r#type<S: Into<String>>(self, t: S) -> Self {
+        self.map(|b| b.r#type(t))
+    }
+}
+
+impl<T> RequestBuilder<T, ApiError, Follows> {
+    pub fn first(self, first: u32) -> Self {
+        self.map(|b| b.first(first))
+    }
+}
+
+impl RequestBuilder<PaginationContainer<Follower>, ApiError, Follows> {
+    pub fn user<'a, Id: Into<UserId<'a>>>(self, user: Id) -> Self {
+        self.map(|b| b.user(user))
+    }
+}
+
+impl RequestBuilder<PaginationContainer<FollowedChannel>, ApiError, Follows> {
+    pub fn broadcaster<'a, Id: Into<BroadcasterId<'a>>>(self, broadcaster: Id) -> Self {
+        self.map(|b| b.broadcaster(broadcaster))
+    }
+}
+
+impl<T> RequestBuilder<T, ApiError, Subscriptions> {
+    pub fn first(self, first: u32) -> Self {
+        self.map(|b| b.first(first))
+    }
+
+    pub fn users<Id: ToString>(self, users: &[Id]) -> Self {
+        self.map(|b| b.users(users))
+    }
+}
+
+impl RequestBuilder<BitsLeaderboard, ApiError, Bits> {
+    pub fn count(self, count: u32) -> Self {
+        self.map(|b| b.count(count))
+    }
+
+    pub fn period(self, period: LeaderboardPeriod) -> Self {
+        self.map(|b| b.period(period))
+    }
+
+    pub fn started_at(self, started_at: DateTime<Utc>) -> Self {
+        self.map(|b| b.started_at(started_at))
+    }
+
+    pub fn user<'a, Id: Into<UserId<'a>>>(self, user: Id) -> Self {
+        self.map(|b| b.user(user))
+    }
+}
+
+impl RequestBuilder<DataContainer<Cheermote>, ApiError, Bits> {
+    pub fn broadcaster<'a, Id: Into<BroadcasterId<'a>>>(self, broadcaster: Id) -> Self {
+        self.map(|b| b.broadcaster(broadcaster))
+    }
+}
+
+impl RequestBuilder<PaginationContainer<ExtensionTransaction>, ApiError, Bits> {
+    pub fn first(self, first: u32) -> Self {
+        self.map(|b| b.first(first))
+    }
+
+    pub fn ids<Id: ToString>(self, ids: &[Id]) -> Self {
+        self.map(|b| b.ids(ids))
+    }
+}
+
+impl Builder<ChannelUpdate> {
+    pub fn title<S: Into<String>>(self, title: S) -> Self {
+        self.map(|b| b.title(title))
+    }
+
+    pub fn game<'a, Id: Into<GameId<'a>>>(self, id: Id) -> Self {
+        self.map(|b| b.game(id))
+    }
+
+    pub fn language<S: Into<String>>(self, language: S) -> Self {
+        self.map(|b| b.language(language))
+    }
+
+    pub fn delay(self, delay: u32) -> Self {
+        self.map(|b| b.delay(delay))
+    }
+
+    pub fn tags<S: ToString>(self, tags: &[S]) -> Self {
+        self.map(|b| b.tags(tags))
+    }
+
+    pub fn content_classification_label(
+        self,
+        label: ContentClassificationLabel,
+        enabled: bool,
+    ) -> Self {
+        self.map(|b| b.content_classification_label(label, enabled))
+    }
+
+    pub fn branded_content(self, branded: bool) -> Self {
+        self.map(|b| b.branded_content(branded))
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.inner.validate()
+    }
+}
+
+impl Builder<MarkerCreate> {
+    pub fn description<S: Into<String>>(self, description: S) -> Self {
+        self.map(|b| b.description(description))
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.inner.validate()
+    }
+}
+
+impl Builder<SubscriptionCheck> {
+    pub fn as_user(self, token: &str) -> Self {
+        self.map(|b| b.as_user(token))
+    }
+}
+
+pub struct Namespace<T> {
+    client: HelixClient,
+    _type: PhantomData<T>,
+}
+
+impl<T> Namespace<T> {
+    fn inner(&self) -> AsyncNamespace<T> {
+        AsyncNamespace::new(&self.client.inner)
+    }
+}
+
+impl HelixClient {
+    pub fn users(&self) -> Namespace<Users> {
+        self.namespace()
+    }
+
+    pub fn streams(&self) -> Namespace<Streams> {
+        self.namespace()
+    }
+
+    pub fn clips(&self) -> Namespace<Clips> {
+        self.namespace()
+    }
+
+    pub fn videos(&self) -> Namespace<Videos> {
+        self.namespace()
+    }
+
+    pub fn channels(&self) -> Namespace<Channels> {
+        self.namespace()
+    }
+
+    pub fn games(&self) -> Namespace<Games> {
+        self.namespace()
+    }
+
+    pub fn search(&self) -> Namespace<Search> {
+        self.namespace()
+    }
+
+    pub fn subscriptions(&self) -> Namespace<Subscriptions> {
+        self.namespace()
+    }
+
+    pub fn bits(&self) -> Namespace<Bits> {
+        self.namespace()
+    }
+}
+
+impl Namespace<Users> {
+    pub fn users<S1: ToString, S2: ToString>(
+        self,
+        ids: &[S1],
+        logins: &[S2],
+    ) -> RequestBuilder<DataContainer<User>> {
+        self.client.wrap(self.inner().users(ids, logins))
+    }
+}
+
+impl Namespace<Streams> {
+    pub fn get(self) -> RequestBuilder<PaginationContainer<Stream>, ApiError, Streams> {
+        self.client.wrap(self.inner().get())
+    }
+
+    pub fn create_marker<'a, Id: Into<UserId<'a>>>(self, user: Id) -> Builder<MarkerCreate> {
+        self.client.wrap_builder(self.inner().create_marker(user))
+    }
+
+    pub fn markers_by_user<'a, Id: Into<UserId<'a>>>(
+        self,
+        user: Id,
+    ) -> RequestBuilder<PaginationContainer<UserMarkers>, ApiError, Markers> {
+        self.client.wrap(self.inner().markers_by_user(user))
+    }
+
+    pub fn markers_by_video<'a, Id: Into<VideoId<'a>>>(
+        self,
+        video: Id,
+    ) -> RequestBuilder<PaginationContainer<UserMarkers>, ApiError, Markers> {
+        self.client.wrap(self.inner().markers_by_video(video))
+    }
+}
+
+impl Namespace<Clips> {
+    pub fn by_game<'a, Id: Into<GameId<'a>>>(
+        self,
+        id: Id,
+    ) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
+        self.client.wrap(self.inner().by_game(id))
+    }
+
+    pub fn by_broadcaster<'a, Id: Into<BroadcasterId<'a>>>(
+        self,
+        id: Id,
+    ) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
+        self.client.wrap(self.inner().by_broadcaster(id))
+    }
+
+    pub fn by_clips<Id: ToString>(
+        self,
+        ids: &[Id],
+    ) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
+        self.client.wrap(self.inner().by_clips(ids))
+    }
+
+    pub fn create<'a, Id: Into<BroadcasterId<'a>>>(
+        self,
+        broadcaster: Id,
+        has_delay: bool,
+    ) -> RequestBuilder<DataContainer<CreatedClip>, ApiError, DefaultOpts> {
+        self.client
+            .wrap(self.inner().create(broadcaster, has_delay))
+    }
+
+    /// Wait until the clip `id` has been created. See the asynchronous client
+    pub fn wait_for_clip<'a, Id: Into<ClipId<'a>>>(self, id: Id) -> Result<Option<Clip>, Error> {
+        self.client.runtime.block_on(self.inner().wait_for_clip(id))
+    }
+}
+
+impl Namespace<Videos> {
+    pub fn by_id<S: ToString>(self, ids: &[S]) -> RequestBuilder<DataContainer<Video>> {
+        self.client.wrap(self.inner().by_id(ids))
+    }
+
+    pub fn by_user<'a, S: Into<UserId<'a>>>(
+        self,
+        user_id: S,
+    ) -> RequestBuilder<PaginationContainer<Video>, ApiError, Videos> {
+        self.client.wrap(self.inner().by_user(user_id))
+    }
+
+    pub fn for_game<'a, S: Into<GameId<'a>>>(
+        self,
+        game_id: S,
+    ) -> RequestBuilder<PaginationContainer<Video>, ApiError, Videos> {
+        self.client.wrap(self.inner().for_game(game_id))
+    }
+}
+
+impl Namespace<Channels> {
+    pub fn channel<'a, Id: Into<BroadcasterId<'a>>>(
+        self,
+        id: Id,
+    ) -> RequestBuilder<DataContainer<Channel>> {
+        self.client.wrap(self.inner().channel(id))
+    }
+
+    pub fn by_ids<Id: ToString>(self, ids: &[Id]) -> RequestBuilder<DataContainer<Channel>> {
+        self.client.wrap(self.inner().by_ids(ids))
+    }
+
+    pub fn update<'a, Id: Into<BroadcasterId<'a>>>(self, id: Id) -> Builder<ChannelUpdate> {
+        self.client.wrap_builder(self.inner().update(id))
+    }
+
+    pub fn followers<'a, Id: Into<BroadcasterId<'a>>>(
+        self,
+        id: Id,
+    ) -> RequestBuilder<PaginationContainer<Follower>, ApiError, Follows> {
+        self.client.wrap(self.inner().followers(id))
+    }
+
+    pub fn followed<'a, Id: Into<UserId<'a>>>(
+        self,
+        user: Id,
+    ) -> RequestBuilder<PaginationContainer<FollowedChannel>, ApiError, Follows> {
+        self.client.wrap(self.inner().followed(user))
+    }
+}
+
+impl Namespace<Games> {
+    pub fn by_ids<Id: ToString>(self, ids: &[Id]) -> RequestBuilder<DataContainer<Game>> {
+        self.client.wrap(self.inner().by_ids(ids))
+    }
+
+    pub fn by_names<S: ToString>(self, names: &[S]) -> RequestBuilder<DataContainer<Game>> {
+        self.client.wrap(self.inner().by_names(names))
+    }
+
+    pub fn by_igdb_ids<Id: ToString>(self, ids: &[Id]) -> RequestBuilder<DataContainer<Game>> {
+        self.client.wrap(self.inner().by_igdb_ids(ids))
+    }
+
+    pub fn top(self) -> RequestBuilder<PaginationContainer<Game>, ApiError, Games> {
+        self.client.wrap(self.inner().top())
+    }
+}
+
+impl Namespace<Search> {
+    pub fn categories(
+        self,
+        query: &str,
+    ) -> RequestBuilder<PaginationContainer<Game>, ApiError, Search> {
+        self.client.wrap(self.inner().categories(query))
+    }
+
+    pub fn channels(
+        self,
+        query: &str,
+    ) -> RequestBuilder<PaginationContainer<SearchChannel>, ApiError, Search> {
+        self.client.wrap(self.inner().channels(query))
+    }
+}
+
+impl Namespace<Subscriptions> {
+    pub fn broadcaster<'a, Id: Into<BroadcasterId<'a>>>(
+        self,
+        id: Id,
+    ) -> RequestBuilder<BroadcasterSubscriptions, ApiError, Subscriptions> {
+        self.client.wrap(self.inner().broadcaster(id))
+    }
+
+    pub fn check<'a, 'b, B: Into<BroadcasterId<'a>>, U: Into<UserId<'b>>>(
+        self,
+        broadcaster: B,
+        user: U,
+    ) -> Builder<SubscriptionCheck> {
+        self.client
+            .wrap_builder(self.inner().check(broadcaster, user))
+    }
+}
+
+impl Namespace<Bits> {
+    pub fn leaderboard(self) -> RequestBuilder<BitsLeaderboard, ApiError, Bits> {
+        self.client.wrap(self.inner().leaderboard())
+    }
+
+    pub fn cheermotes(self) -> RequestBuilder<DataContainer<Cheermote>, ApiError, Bits> {
+        self.client.wrap(self.inner().cheermotes())
+    }
+
+    pub fn extension_transactions<S: ToString>(
+        self,
+        extension_id: S,
+    ) -> RequestBuilder<PaginationContainer<ExtensionTransaction>, ApiError, Bits> {
+        self.client
+            .wrap(self.inner().extension_transactions(extension_id))
+    }
+}
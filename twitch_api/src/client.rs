@@ -6,19 +6,28 @@ use std::sync::Arc;
 use std::future::IntoFuture;
 use std::time::Duration;
 
-use crate::error::Error;
-use crate::helix::limiter::BucketLimiter;
+use crate::connector::{self, Connector, ProxyConnector};
+use crate::env;
+use crate::error::{Error, ValidationError};
+use crate::helix::cache::CacheConfig;
+use crate::helix::inflight::InflightRequests;
+use crate::helix::limiter::{BucketLimiter, TokenBuckets};
 use crate::helix::models::ApiError;
 use crate::helix::models::Credentials;
-use crate::namespace::auth::client_credentials;
-use hyper::body::Body;
+use crate::helix::token_store::TokenStore;
+use crate::namespace::auth::{client_credentials, refresh_token as refresh_token_grant, validate};
+use hyper::body::{Body, Bytes, HttpBody};
 use hyper::client::{Client as HyperClient, HttpConnector};
 use hyper::Method;
 use hyper::Request;
-use hyper_tls::HttpsConnector;
+use hyper::StatusCode;
+use hyper::Uri;
 
+use futures::future::try_join_all;
 use futures::Future;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
+use twitch_types::UserId;
 
 use serde::de::DeserializeOwned;
 
@@ -27,6 +36,8 @@ use std::collections::BTreeMap;
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub enum RatelimitKey {
     Default,
+    /// Clip creation, limited separately in addition to the default bucket
+    ClipCreation,
 }
 
 #[derive(Debug)]
@@ -37,15 +48,25 @@ pub struct RatelimitMap {
 const API_HELIX_BASE_URI: &str = "https://api.twitch.tv/helix";
 const AUTH_BASE_URI: &str = "https://id.twitch.tv/oauth2";
 
+/// Maximum number of ids Helix accepts in a single request
+pub const MAX_BATCH_SIZE: usize = 100;
+
 /// Endpoint supports multiple pages of results
 pub trait ForwardPagination {
     fn cursor<'a>(&'a self) -> Option<&'a str>;
 }
 
+/* Endpoints that respond with 204 No Content */
+impl ForwardPagination for () {
+    fn cursor(&self) -> Option<&str> {
+        None
+    }
+}
+
 pub struct DefaultOpts {}
 
 /// Endpoint supports multiple pages of results.
-/// Can move backwards given the current cursor
+/// Can move backwards given the cursor used to obtain the current page
 pub trait BidirectionalPagination<T, E> {
     fn next(&self) -> Option<IterableApiRequest<T, E>>;
     fn prev(&self) -> Option<IterableApiRequest<T, E>>;
@@ -60,6 +81,12 @@ pub trait PaginationContrainerTrait {
 
 pub trait HelixPagination {}
 
+/// Response that can be assembled from the responses of several smaller
+/// requests. Used to split queries that exceed Helix's limit on ids
+pub trait BatchedResponse: Sized {
+    fn merge(parts: Vec<Self>) -> Self;
+}
+
 pub type ParamList<'a> = BTreeMap<&'a str, &'a dyn ToString>;
 
 #[derive(Clone, Debug)]
@@ -81,12 +108,16 @@ pub enum HelixScope {
     AnalyticsReadExtensions,
     AnalyticsReadGames,
     BitsRead,
+    ChannelManageBroadcast,
     ChannelReadSubscriptions,
     ClipsEdit,
+    ModeratorReadFollowers,
     UserEdit,
     UserEditBroadcast,
     UserReadBroadcast,
     UserReadEmail,
+    UserReadFollows,
+    UserReadSubscriptions,
 }
 
 impl HelixScope {
@@ -96,17 +127,21 @@ impl HelixScope {
             AnalyticsReadExtensions => "analytics:read:extensions",
             AnalyticsReadGames => "analytics:read:games",
             BitsRead => "bits:read",
+            ChannelManageBroadcast => "channel:manage:broadcast",
             ChannelReadSubscriptions => "channel:read:subscriptions",
             ClipsEdit => "clips:edit",
+            ModeratorReadFollowers => "moderator:read:followers",
             UserEdit => "user:edit",
             UserEditBroadcast => "user:edit:broadcast",
             UserReadBroadcast => "user:read:broadcast",
             UserReadEmail => "user:read:email",
+            UserReadFollows => "user:read:follows",
+            UserReadSubscriptions => "user:read:subscriptions",
         }
     }
 }
 
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 impl<'de> Deserialize<'de> for HelixScope {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -118,6 +153,12 @@ impl<'de> Deserialize<'de> for HelixScope {
     }
 }
 
+impl Serialize for HelixScope {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_str())
+    }
+}
+
 impl TryFrom<&str> for HelixScope {
     type Error = ScopeParseError;
     fn try_from(s: &str) -> Result<HelixScope, Self::Error> {
@@ -126,12 +167,16 @@ impl TryFrom<&str> for HelixScope {
             "analytics:read:extensions" => AnalyticsReadExtensions,
             "analytics:read:games" => AnalyticsReadGames,
             "bits:read" => BitsRead,
+            "channel:manage:broadcast" => ChannelManageBroadcast,
             "channel:read:subscriptions" => ChannelReadSubscriptions,
             "clips:edit" => ClipsEdit,
+            "moderator:read:followers" => ModeratorReadFollowers,
             "user:edit" => UserEdit,
             "user:edit:broadcast" => UserEditBroadcast,
             "user:read:broadcast" => UserReadBroadcast,
             "user:read:email" => UserReadEmail,
+            "user:read:follows" => UserReadFollows,
+            "user:read:subscriptions" => UserReadSubscriptions,
             _ => return Err(ScopeParseError {}),
         })
     }
@@ -159,12 +204,17 @@ enum ClientType {
 
 #[derive(Debug)]
 pub struct ClientConfig {
-    pub hyper: HyperClient<HttpsConnector<HttpConnector>>,
+    pub hyper: HyperClient<Connector>,
     pub api_base_uri: String,
     pub auth_base_uri: String,
     pub ratelimits: RatelimitMap,
     pub max_retrys: u32,
+    /// Limit for a whole request, from connecting until the response is read
     pub timeout: Duration,
+    /// Limit for each wait for data of a response body
+    pub read_timeout: Option<Duration>,
+    /// Cache GET responses. Cache hits are not counted against the ratelimit
+    pub cache: Option<CacheConfig>,
 }
 
 impl Default for RatelimitMap {
@@ -179,6 +229,15 @@ impl Default for RatelimitMap {
                 &"ratelimit-reset",
             ),
         );
+        limits.insert(
+            RatelimitKey::ClipCreation,
+            BucketLimiter::new(
+                600,
+                &"ratelimit-helixclipscreation-limit",
+                &"ratelimit-helixclipscreation-remaining",
+                &"ratelimit-reset",
+            ),
+        );
         RatelimitMap { inner: limits }
     }
 }
@@ -193,25 +252,228 @@ impl RatelimitMap {
 
 impl Default for ClientConfig {
     fn default() -> Self {
-        let ratelimits = RatelimitMap::default();
-        let https = HttpsConnector::new();
-        let hyper = HyperClient::builder().build::<_, Body>(https);
+        ClientConfig::builder()
+            .build()
+            .expect("default client configuration is valid")
+    }
+}
+
+impl ClientConfig {
+    pub fn builder() -> ClientConfigBuilder {
+        ClientConfigBuilder::default()
+    }
+
+    /// Default configuration with overrides from the environment.
+    ///
+    /// See the `env` module for the variables that are read
+    pub fn from_env() -> Result<ClientConfig, Error> {
+        ClientConfig::builder().from_env()?.build()
+    }
+}
+
+/// Builds a `ClientConfig` and the http client it uses
+#[derive(Debug)]
+pub struct ClientConfigBuilder {
+    api_base_uri: String,
+    auth_base_uri: String,
+    proxy: Option<String>,
+    connect_timeout: Option<Duration>,
+    timeout: Duration,
+    read_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    http2_only: bool,
+    root_certificates: Vec<Vec<u8>>,
+    ratelimits: RatelimitMap,
+    max_retrys: u32,
+    cache: Option<CacheConfig>,
+}
 
-        ClientConfig {
-            hyper: hyper,
+impl Default for ClientConfigBuilder {
+    fn default() -> Self {
+        ClientConfigBuilder {
             api_base_uri: API_HELIX_BASE_URI.to_owned(),
             auth_base_uri: AUTH_BASE_URI.to_owned(),
-            ratelimits,
-            max_retrys: 1,
+            proxy: None,
+            connect_timeout: None,
             timeout: Duration::from_secs(10),
+            read_timeout: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            http2_only: false,
+            root_certificates: Vec::new(),
+            ratelimits: RatelimitMap::default(),
+            max_retrys: 1,
+            cache: None,
         }
     }
 }
 
+impl ClientConfigBuilder {
+    /// Base uri of the Helix api. Defaults to https://api.twitch.tv/helix
+    pub fn api_base_uri<S: Into<String>>(mut self, uri: S) -> Self {
+        self.api_base_uri = uri.into();
+        self
+    }
+
+    /// Base uri of the OAuth api. Defaults to https://id.twitch.tv/oauth2
+    pub fn auth_base_uri<S: Into<String>>(mut self, uri: S) -> Self {
+        self.auth_base_uri = uri.into();
+        self
+    }
+
+    /// Route all requests through an http proxy, i.e. http://localhost:3128
+    pub fn proxy<S: Into<String>>(mut self, uri: S) -> Self {
+        self.proxy = Some(uri.into());
+        self
+    }
+
+    /// Maximum time to establish a connection. Unlimited by default
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum time for a request, from connecting until the whole response
+    /// is read. Defaults to 10 seconds
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Maximum time to wait for the next part of a response body, so a
+    /// stalled response fails before `timeout`. Unlimited by default
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum number of idle connections kept per host
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// How long idle connections are kept open
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Only use HTTP/2 connections
+    pub fn http2_only(mut self, enabled: bool) -> Self {
+        self.http2_only = enabled;
+        self
+    }
+
+    /// Trust an additional PEM encoded root certificate
+    pub fn add_root_certificate<B: Into<Vec<u8>>>(mut self, pem: B) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    pub fn ratelimits(mut self, ratelimits: RatelimitMap) -> Self {
+        self.ratelimits = ratelimits;
+        self
+    }
+
+    /// Number of times a failed request is retried. Defaults to 1
+    pub fn max_retrys(mut self, max_retrys: u32) -> Self {
+        self.max_retrys = max_retrys;
+        self
+    }
+
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Override options with the values of environment variables.
+    ///
+    /// See the `env` module for the variables that are read
+    pub fn from_env(self) -> Result<Self, Error> {
+        env::load_dotenv();
+        env::configure(self)
+    }
+
+    pub fn build(self) -> Result<ClientConfig, Error> {
+        let api_base_uri = validate_base_uri(&self.api_base_uri)?;
+        let auth_base_uri = validate_base_uri(&self.auth_base_uri)?;
+        let proxy = match &self.proxy {
+            Some(proxy) => Some(validate_proxy_uri(proxy)?),
+            None => None,
+        };
+
+        let mut http = HttpConnector::new();
+        http.set_connect_timeout(self.connect_timeout);
+        let connector = connector::build(
+            ProxyConnector::new(http, proxy),
+            &self.root_certificates,
+            self.http2_only,
+        )?;
+
+        let mut hyper = HyperClient::builder();
+        hyper.http2_only(self.http2_only);
+        if let Some(max) = self.pool_max_idle_per_host {
+            hyper.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            hyper.pool_idle_timeout(timeout);
+        }
+
+        Ok(ClientConfig {
+            hyper: hyper.build::<_, Body>(connector),
+            api_base_uri,
+            auth_base_uri,
+            ratelimits: self.ratelimits,
+            max_retrys: self.max_retrys,
+            timeout: self.timeout,
+            read_timeout: self.read_timeout,
+            cache: self.cache,
+        })
+    }
+}
+
+/* Base uris are joined with endpoint paths so they must not end with '/' */
+fn validate_base_uri(uri: &str) -> Result<String, Error> {
+    let parsed = url::Url::parse(uri)
+        .map_err(|e| Error::config_error(format!("invalid base uri {}: {}", uri, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host().is_none() {
+        return Err(Error::config_error(format!(
+            "base uri {} must be an absolute http or https uri",
+            uri
+        )));
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err(Error::config_error(format!(
+            "base uri {} must not contain a query or fragment",
+            uri
+        )));
+    }
+
+    Ok(uri.trim_end_matches('/').to_owned())
+}
+
+fn validate_proxy_uri(uri: &str) -> Result<Uri, Error> {
+    let parsed = uri
+        .parse::<Uri>()
+        .map_err(|e| Error::config_error(format!("invalid proxy uri {}: {}", uri, e)))?;
+    if parsed.scheme_str() != Some("http") || parsed.host().is_none() {
+        return Err(Error::config_error(format!(
+            "proxy uri {} must be an absolute http uri",
+            uri
+        )));
+    }
+
+    Ok(parsed)
+}
+
 #[derive(Debug)]
 pub struct UnauthClient {
     id: String,
     config: ClientConfig,
+    inflight: InflightRequests,
+    token_buckets: TokenBuckets,
 }
 
 #[derive(Debug)]
@@ -219,7 +481,9 @@ pub struct AuthClient {
     credentials: Credentials,
     secret: String,
     previous: Client,
-    scopes: Vec<HelixScope>,
+    /* Buckets of a user token given with `as_user`. Other clients use the
+     * buckets of the config */
+    ratelimits: HashMap<RatelimitKey, BucketLimiter>,
 }
 
 pub trait ClientTrait {
@@ -351,6 +615,9 @@ impl ClientTrait for AuthClient {
     }
 
     fn ratelimit<'a>(&'a self, key: RatelimitKey) -> Option<&'a BucketLimiter> {
+        if let Some(bucket) = self.ratelimits.get(&key) {
+            return Some(bucket);
+        }
         match self.previous.inner.as_ref() {
             ClientType::Auth(auth) => auth.ratelimit(key),
             ClientType::Unauth(unauth) => unauth.ratelimit(key),
@@ -362,7 +629,7 @@ impl ClientTrait for AuthClient {
     }
 
     fn scopes(&self) -> &[HelixScope] {
-        &self.scopes
+        &self.credentials.scope
     }
 }
 
@@ -372,6 +639,65 @@ impl Client {
             inner: Arc::new(ClientType::Unauth(UnauthClient {
                 id: id.into(),
                 config: config,
+                inflight: InflightRequests::default(),
+                token_buckets: TokenBuckets::default(),
+            })),
+        }
+    }
+
+    /// A client that authenticates with the user access `token`.
+    ///
+    /// The client shares the connection pool, configuration and in-flight
+    /// requests of this client. Requests are counted against a ratelimit
+    /// bucket of the token, shared by all clients using the same token.
+    pub fn as_user(&self, token: &str) -> Client {
+        let owner = format!("token {}", token_digest(token));
+        self.user_client(token, Vec::new(), Some(owner))
+    }
+
+    /* Twitch limits each user rather than each token, so the buckets of a
+     * known user outlive refreshes of their token */
+    pub(crate) fn as_user_with_scopes(
+        &self,
+        user: &UserId<'_>,
+        token: &str,
+        scopes: Vec<HelixScope>,
+    ) -> Client {
+        let owner = format!("user {}", user.as_ref());
+        self.user_client(token, scopes, Some(owner))
+    }
+
+    /// A client that authenticates with `token`, but counts requests against
+    /// the buckets of the application. For endpoints outside of Helix, such
+    /// as token validation
+    pub(crate) fn with_token(&self, token: &str) -> Client {
+        self.user_client(token, Vec::new(), None)
+    }
+
+    fn user_client(&self, token: &str, scopes: Vec<HelixScope>, owner: Option<String>) -> Client {
+        let bottom = self.get_bottom_client();
+        let ratelimits = match (bottom.inner.as_ref(), owner) {
+            (ClientType::Unauth(inner), Some(owner)) => inner
+                .config
+                .ratelimits
+                .inner
+                .iter()
+                .map(|(key, template)| {
+                    let bucket = inner.token_buckets.get(&owner, key.clone(), template);
+                    (key.clone(), bucket)
+                })
+                .collect(),
+            _ => HashMap::new(),
+        };
+
+        let mut credentials = Credentials::new(token, None);
+        credentials.scope = scopes;
+        Client {
+            inner: Arc::new(ClientType::Auth(AuthClient {
+                credentials,
+                secret: self.secret().unwrap_or_default().to_owned(),
+                previous: bottom,
+                ratelimits,
             })),
         }
     }
@@ -397,12 +723,24 @@ impl Client {
             ClientType::Unauth(_) => self.clone(),
         }
     }
+
+    /* In-flight requests are tracked by the bottom client so they are shared
+     * by all clients stacked on top of it
+     */
+    fn inflight(&self) -> &InflightRequests {
+        match self.inner.as_ref() {
+            ClientType::Auth(inner) => inner.previous.inflight(),
+            ClientType::Unauth(inner) => &inner.inflight,
+        }
+    }
 }
 
 pub struct AuthClientBuilder {
     scopes: HashSet<HelixScope>,
     secret: String,
     token: Option<String>,
+    refresh_token: Option<String>,
+    store: Option<(Arc<dyn TokenStore>, String)>,
     client: Client,
     /*If the user supplies a token,
      * then we can skip fetching it from the server and are authenticated
@@ -416,23 +754,46 @@ impl AuthClientBuilder {
             client: client,
             secret: secret.into(),
             token: None,
+            refresh_token: None,
+            store: None,
         }
     }
 
     pub async fn build(self) -> Result<Client, Error> {
         let old_client = self.client.clone();
-        let cred = client_credentials(self.client.clone(), &self.secret).await;
-        if let Err(e) = cred {
-            return Err(Error::from(e));
+
+        let stored = match (&self.token, &self.store) {
+            (None, Some((store, key))) => self.load_stored(store.as_ref(), key).await?,
+            _ => None,
+        };
+
+        /* Stored tokens are already up to date in the store */
+        let (cred, save) = match (self.token, stored) {
+            (Some(token), _) => {
+                let mut cred = Credentials::new(token, self.refresh_token);
+                cred.scope = self.scopes.into_iter().collect();
+                (cred, true)
+            }
+            (None, Some(cred)) => (cred, false),
+            (None, None) => {
+                let cred = client_credentials(self.client.clone(), &self.secret).await;
+                if let Err(e) = cred {
+                    return Err(Error::from(e));
+                }
+                (cred.unwrap(), true)
+            }
+        };
+
+        if let (Some((store, key)), true) = (&self.store, save) {
+            store.save(key, &cred).await?;
         }
-        let cred = cred.unwrap();
 
         Ok(Client {
             inner: Arc::new(ClientType::Auth(AuthClient {
                 credentials: cred,
                 secret: self.secret,
                 previous: old_client,
-                scopes: Vec::new(),
+                ratelimits: HashMap::new(),
             })),
         })
     }
@@ -455,6 +816,77 @@ impl AuthClientBuilder {
         self.token.replace(token.to_owned());
         self
     }
+
+    /// Refresh token belonging to the token supplied with `token`
+    pub fn refresh_token(mut self, token: &str) -> AuthClientBuilder {
+        self.refresh_token.replace(token.to_owned());
+        self
+    }
+
+    /// Keep the token in `store` under `key`.
+    ///
+    /// Without a token given with `token` the stored token is used when it is
+    /// still valid or can be refreshed. The token in use is saved to the
+    /// store, including refreshed ones.
+    ///
+    /// A stored token that is rejected and can't be refreshed is deleted and
+    /// `build` fails with an error for which `is_stored_token_rejected` is
+    /// true. It is not replaced by an app access token, as the store may have
+    /// held a user's token. Building again starts over without a stored token
+    pub fn token_store<S: Into<String>>(
+        mut self,
+        store: Arc<dyn TokenStore>,
+        key: S,
+    ) -> AuthClientBuilder {
+        self.store = Some((store, key.into()));
+        self
+    }
+
+    /* Stored tokens are validated as they may have been revoked since they
+     * were saved. Expired tokens are refreshed and tokens that can't be used
+     * are deleted. A token without a refresh token can't be refreshed */
+    async fn load_stored(
+        &self,
+        store: &dyn TokenStore,
+        key: &str,
+    ) -> Result<Option<Credentials>, Error> {
+        let mut cred = match store.load(key).await? {
+            Some(cred) => cred,
+            None => return Ok(None),
+        };
+
+        let refresh_token = if cred.is_expired() {
+            cred.refresh_token.take()
+        } else {
+            match validate(self.client.clone(), &cred.access_token).await {
+                Ok(validation) => {
+                    cred.set_expires_in(validation.expires_in);
+                    return Ok(Some(cred));
+                }
+                Err(e) if e.is_token_rejected() => cred.refresh_token.take(),
+                Err(e) => return Err(e),
+            }
+        };
+
+        let refreshed = match refresh_token {
+            Some(token) => refresh_token_grant(self.client.clone(), &self.secret, &token)
+                .await
+                .map(Some),
+            None => Ok(None),
+        };
+        match refreshed {
+            Ok(Some(cred)) => {
+                store.save(key, &cred).await?;
+                return Ok(Some(cred));
+            }
+            Ok(None) => {}
+            Err(e) if e.is_token_rejected() => {}
+            Err(e) => return Err(e),
+        }
+
+        store.delete(key).await?;
+        Err(Error::stored_token_rejected(key))
+    }
 }
 
 #[derive(Debug)]
@@ -463,7 +895,12 @@ pub struct RequestRef {
     params: Vec<(String, String)>,
     client: Client,
     ratelimit: Option<BucketLimiter>,
+    /* Bucket of endpoints that Twitch limits separately. Requests to them
+     * count against both buckets */
+    endpoint_ratelimit: Option<BucketLimiter>,
     method: Method,
+    body: Option<serde_json::Value>,
+    cache: bool,
 }
 
 impl RequestRef {
@@ -480,8 +917,15 @@ impl RequestRef {
             client,
             method,
             ratelimit,
+            endpoint_ratelimit: None,
+            body: None,
+            cache: true,
         }
     }
+
+    fn ratelimits(&self) -> impl Iterator<Item = &BucketLimiter> {
+        self.ratelimit.iter().chain(self.endpoint_ratelimit.iter())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -490,6 +934,7 @@ pub struct ApiRequest<T, E> {
     max_attempts: u32,
     pagination: Option<String>,
     forward: bool,
+    coalesce: bool,
     _marker: PhantomData<T>,
     _error_type: PhantomData<E>,
 }
@@ -501,6 +946,13 @@ pub struct RequestBuilder<T, E = ApiError, Opts = DefaultOpts> {
     method: Method,
     ratelimit: Option<BucketLimiter>,
     ratelimit_cost: u32,
+    endpoint_ratelimit: Option<RatelimitKey>,
+    coalesce: bool,
+    cache: bool,
+    body: Option<serde_json::Value>,
+    batch: Vec<(String, String)>,
+    batched: HashSet<(String, String)>,
+    merge: Option<fn(Vec<T>) -> T>,
     _data_type: PhantomData<T>,
     _error_type: PhantomData<E>,
     _opts: PhantomData<Opts>,
@@ -525,6 +977,69 @@ impl<T, E, Opt> RequestBuilder<T, E, Opt> {
         self.ratelimit_cost = cost;
         self
     }
+
+    /// Count the request against the bucket `key` of the client, in addition
+    /// to its regular bucket. For endpoints Twitch limits separately
+    pub fn with_endpoint_ratelimit(mut self, key: RatelimitKey) -> Self {
+        self.endpoint_ratelimit = Some(key);
+        self
+    }
+
+    /// Send the request with the user access `token` instead of the client's
+    /// token. See `Client::as_user`.
+    ///
+    /// The request uses the ratelimit bucket of the token, replacing a bucket
+    /// set before with `with_ratelimit`
+    pub fn as_user(mut self, token: &str) -> Self {
+        self.client = self.client.as_user(token);
+        self.ratelimit = self
+            .client
+            .ratelimit(RatelimitKey::Default)
+            .map(|m| m.to_owned());
+        self
+    }
+
+    /// Share the response of an identical GET request that is already in
+    /// flight instead of sending another one. Enabled by default
+    pub fn coalesce(mut self, coalesce: bool) -> Self {
+        self.coalesce = coalesce;
+        self
+    }
+
+    /// Read and store the response in the response cache of the client, if
+    /// one is configured. Enabled by default
+    pub fn cache(mut self, cache: bool) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Send `body` as the JSON body of the request
+    pub fn with_body(mut self, body: serde_json::Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+}
+
+impl<T: BatchedResponse, E, Opt> RequestBuilder<T, E, Opt> {
+    /// Add a query parameter for each value. Duplicate values are dropped.
+    ///
+    /// All batched parameters of a request share Helix's limit of 100 values.
+    /// When there are more, the request is split into several requests that
+    /// are performed concurrently and their responses merged.
+    pub fn with_batched_query<S: Into<String>, I: IntoIterator>(mut self, key: S, values: I) -> Self
+    where
+        I::Item: ToString,
+    {
+        let key = key.into();
+        for value in values {
+            let pair = (key.clone(), value.to_string());
+            if self.batched.insert(pair.clone()) {
+                self.batch.push(pair);
+            }
+        }
+        self.merge = Some(T::merge);
+        self
+    }
 }
 
 impl<
@@ -542,21 +1057,77 @@ impl<
                 .map(|m| m.to_owned()),
             client: client,
             ratelimit_cost: 1,
+            endpoint_ratelimit: None,
             method: method,
+            coalesce: true,
+            cache: true,
+            body: None,
+            batch: Vec::new(),
+            batched: HashSet::new(),
+            merge: None,
             _data_type: PhantomData,
             _error_type: PhantomData,
             _opts: PhantomData,
         }
     }
 
-    pub fn build(self) -> ApiRequest<T, E> {
-        ApiRequest::new(
-            self.url,
-            self.params,
-            self.client,
-            self.method,
-            self.ratelimit,
-        )
+    /// Build a single request. Batched parameters are included as is,
+    /// regardless of their count
+    pub fn build(mut self) -> ApiRequest<T, E> {
+        let mut params = std::mem::take(&mut self.params);
+        params.append(&mut self.batch);
+        self.request(params)
+    }
+
+    /// The http request `build` would send, without sending it.
+    ///
+    /// With `redact` the access token and other credentials are replaced by a
+    /// placeholder. See `curl_command` to render the request
+    pub fn prepare(self, redact: bool) -> Result<Request<Bytes>, Error> {
+        prepare_request(&self.build(), redact)
+    }
+
+    /// Build one request per batch of at most `MAX_BATCH_SIZE` batched
+    /// parameters
+    pub fn build_batches(self) -> Vec<ApiRequest<T, E>> {
+        if self.batch.len() <= MAX_BATCH_SIZE {
+            return vec![self.build()];
+        }
+
+        self.batch
+            .chunks(MAX_BATCH_SIZE)
+            .map(|chunk| {
+                let mut params = self.params.clone();
+                params.extend_from_slice(chunk);
+                self.request(params)
+            })
+            .collect()
+    }
+
+    fn request(&self, params: Vec<(String, String)>) -> ApiRequest<T, E> {
+        let mut inner = RequestRef::new(
+            self.url.clone(),
+            params,
+            self.client.clone(),
+            self.method.clone(),
+            self.ratelimit.clone(),
+        );
+        inner.body = self.body.clone();
+        inner.cache = self.cache;
+        inner.endpoint_ratelimit = self
+            .endpoint_ratelimit
+            .clone()
+            .and_then(|key| self.client.ratelimit(key).cloned());
+
+        ApiRequest {
+            inner: Arc::new(inner),
+            max_attempts: self.client.config().max_retrys,
+            pagination: None,
+            forward: true,
+            coalesce: self.coalesce,
+            _marker: PhantomData,
+            _error_type: PhantomData,
+        }
     }
 }
 
@@ -570,9 +1141,41 @@ impl<
         let r = self.build();
         IterableApiRequest::from_request(&r)
     }
+
+    /// Start at the page after `cursor`, e.g. a cursor kept from an earlier
+    /// response
+    pub fn after<S: Into<String>>(self, cursor: S) -> IterableApiRequest<T, E> {
+        let r = self.build();
+        IterableApiRequest::from_request_with_cursor(r.inner, Some(cursor.into()), true)
+    }
+
+    /// Start at the page before `cursor`
+    pub fn before<S: Into<String>>(self, cursor: S) -> IterableApiRequest<T, E> {
+        let r = self.build();
+        IterableApiRequest::from_request_with_cursor(r.inner, Some(cursor.into()), false)
+    }
 }
 
-fn build_request<T, E>(request: &ApiRequest<T, E>) -> Request<Body> {
+/// Check that the token of `client` has `scope`. Tokens whose scopes are
+/// unknown pass, leaving the check to Twitch
+pub(crate) fn require_scope(client: &Client, scope: HelixScope) -> Result<(), ValidationError> {
+    let scopes = client.scopes();
+    if !scopes.is_empty() && !scopes.contains(&scope) {
+        return Err(ValidationError::MissingScope(scope));
+    }
+    Ok(())
+}
+
+/// Percent-encode a query key or value
+pub(crate) fn encode(s: &str) -> String {
+    url::form_urlencoded::byte_serialize(s.as_bytes()).collect()
+}
+
+/// Query parameters that carry credentials
+const SECRET_PARAMS: &[&str] = &["client_secret", "refresh_token", "access_token"];
+const REDACTED: &str = "REDACTED";
+
+fn request_uri<T, E>(request: &ApiRequest<T, E>, redact: bool) -> String {
     let mut query = String::new();
     let mut uri = request.inner.url.clone();
 
@@ -580,6 +1183,10 @@ fn build_request<T, E>(request: &ApiRequest<T, E>) -> Request<Body> {
         if !query.is_empty() {
             query += "&";
         }
+        let value = match redact && SECRET_PARAMS.contains(&key.as_str()) {
+            true => REDACTED,
+            false => value,
+        };
         query = query + key + "=" + value;
     }
 
@@ -599,80 +1206,250 @@ fn build_request<T, E>(request: &ApiRequest<T, E>) -> Request<Body> {
         uri = uri + "?" + &query;
     }
 
+    uri
+}
+
+/// Build the http request for `request`. With `redact` credentials in the
+/// headers and query are replaced by a placeholder
+fn prepare_request<T, E>(
+    request: &ApiRequest<T, E>,
+    redact: bool,
+) -> Result<Request<Bytes>, Error> {
     let mut builder = Request::builder()
         .method(request.inner.method.clone())
         .header("Client-Id", request.inner.client.id())
-        .uri(uri);
+        .uri(request_uri(request, redact));
 
     if let ClientType::Auth(c) = request.inner.client.inner.as_ref() {
-        builder = builder.header(
-            "Authorization",
-            "Bearer ".to_owned() + &c.credentials.access_token,
-        );
+        let token = match redact {
+            true => REDACTED,
+            false => &c.credentials.access_token,
+        };
+        builder = builder.header("Authorization", "Bearer ".to_owned() + token);
     }
 
-    let req = builder.body(Body::empty()).unwrap();
+    let body = match &request.inner.body {
+        Some(body) => {
+            builder = builder.header("Content-Type", "application/json");
+            Bytes::from(body.to_string())
+        }
+        None => Bytes::new(),
+    };
+
+    Ok(builder.body(body)?)
+}
+
+fn build_request<T, E>(request: &ApiRequest<T, E>) -> Result<Request<Body>, Error> {
+    let req = prepare_request(request, false)?.map(Body::from);
     debug!("{:?}", req);
-    return req;
+    Ok(req)
 }
 
-async fn perform_api_request<
-    T: serde::de::DeserializeOwned + Send,
-    E: serde::de::DeserializeOwned + Send,
->(
-    request: ApiRequest<T, E>,
-) -> Result<T, Error> {
+/// Render `request` as a curl command, e.g. to attach to a bug report.
+///
+/// Use a request prepared with `redact` set unless the credentials should
+/// be part of the command
+pub fn curl_command<B: AsRef<[u8]>>(request: &Request<B>) -> String {
+    fn quote(s: &str) -> String {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+
+    let mut command = format!(
+        "curl -X {} {}",
+        request.method(),
+        quote(&request.uri().to_string())
+    );
+    for (name, value) in request.headers() {
+        let header = format!("{}: {}", name, String::from_utf8_lossy(value.as_bytes()));
+        command = command + " -H " + &quote(&header);
+    }
+
+    let body = request.body().as_ref();
+    if !body.is_empty() {
+        command = command + " --data " + &quote(&String::from_utf8_lossy(body));
+    }
+    command
+}
+
+async fn fetch_response<T, E>(request: ApiRequest<T, E>) -> Result<(StatusCode, Bytes), Error> {
     let mut attempts = 0;
     loop {
-        if let Some(limiter) = &request.inner.ratelimit {
+        let r = build_request(&request)?;
+        for limiter in request.inner.ratelimits() {
             limiter.take(1).await?;
         }
 
-        let r = build_request(&request);
         let c = &request.inner.client.config();
+        let deadline = tokio::time::Instant::now() + c.timeout;
         let f = c.hyper.request(r);
-        let res = tokio::time::timeout(c.timeout, f).await;
+        let res = tokio::time::timeout_at(deadline, f).await;
 
-        if let Some(limiter) = &request.inner.ratelimit {
+        for limiter in request.inner.ratelimits() {
             limiter.restore(1).await?;
         }
 
         let res = res??;
         let (parts, body) = res.into_parts();
 
-        if let Some(limiter) = &request.inner.ratelimit {
+        for limiter in request.inner.ratelimits() {
             limiter.update_from_headers(&parts.headers);
         }
 
-        let body = hyper::body::to_bytes(body).await?;
+        let body = tokio::time::timeout_at(deadline, read_body(body, c.read_timeout)).await??;
         trace!("{:#?}", parts);
         trace!("{:#?}", body);
 
-        if parts.status.is_success() {
-            let value = serde_json::from_slice::<T>(body.as_ref());
-            match value {
-                Ok(v) => return Ok(v),
-                Err(e) => {
-                    trace!("{:#?}", e);
-                    return Err(e.into());
-                }
-            }
+        //TODO: This explicitly check for rate limit errors
+        if !parts.status.is_success() && attempts < request.max_attempts {
+            attempts += 1;
+            continue;
         }
 
-        let value = serde_json::from_slice::<ApiError>(body.as_ref());
-        let res = match value {
-            Ok(v) => Err(v.into()),
-            Err(e) => Err(e.into()),
+        return Ok((parts.status, body));
+    }
+}
+
+async fn read_body(mut body: Body, read_timeout: Option<Duration>) -> Result<Bytes, Error> {
+    let read_timeout = match read_timeout {
+        Some(read_timeout) => read_timeout,
+        None => return Ok(hyper::body::to_bytes(body).await?),
+    };
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = tokio::time::timeout(read_timeout, body.data()).await? {
+        bytes.extend_from_slice(&chunk?);
+    }
+    Ok(Bytes::from(bytes))
+}
+
+async fn perform_api_request<
+    T: serde::de::DeserializeOwned + Send,
+    E: serde::de::DeserializeOwned + Send,
+>(
+    request: ApiRequest<T, E>,
+) -> Result<T, Error> {
+    let cache = cache_entry(&request);
+    if let Some((cache, key, _)) = &cache {
+        if let Some(body) = cache.store().get(key).await {
+            trace!("cache hit {}", key);
+            return Ok(serde_json::from_slice::<T>(body.as_ref())?);
+        }
+    }
+
+    let (status, body) = match coalesce_key(&request) {
+        Some(key) => {
+            let fetch = fetch_response(request.untyped());
+            let inflight = request.inner.client.inflight();
+            inflight.run(key, fetch).await.map_err(Error::from)?
+        }
+        None => fetch_response(request.untyped()).await?,
+    };
+
+    if status.is_success() {
+        let value = match body.is_empty() {
+            true => serde_json::from_slice::<T>(b"null"),
+            false => serde_json::from_slice::<T>(body.as_ref()),
         };
+        return match value {
+            Ok(v) => {
+                match cache {
+                    Some((cache, key, ttl)) if !is_empty_page(&body) => {
+                        cache.store().put(key, body, ttl).await
+                    }
+                    _ => {}
+                }
+                Ok(v)
+            }
+            Err(e) => {
+                trace!("{:#?}", e);
+                Err(e.into())
+            }
+        };
+    }
 
-        //TODO: This explicitly check for rate limit errors
-        if attempts < request.max_attempts {
-            attempts += 1;
-            continue;
+    let value = serde_json::from_slice::<ApiError>(body.as_ref());
+    match value {
+        Ok(v) => Err(v.into()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl<T, E> ApiRequest<T, E> {
+    /* Fetching the response does not depend on the response type. Erasing it
+     * allows the fetch to be shared between callers expecting different types
+     */
+    fn untyped(&self) -> ApiRequest<(), ()> {
+        ApiRequest {
+            inner: self.inner.clone(),
+            max_attempts: self.max_attempts,
+            pagination: self.pagination.clone(),
+            forward: self.forward,
+            coalesce: self.coalesce,
+            _marker: PhantomData,
+            _error_type: PhantomData,
         }
+    }
+}
+
+/// Key identifying requests that can share a single response
+fn coalesce_key<T, E>(request: &ApiRequest<T, E>) -> Option<String> {
+    if !request.coalesce || request.inner.method != Method::GET || request.inner.body.is_some() {
+        return None;
+    }
+
+    let mut key = request.inner.method.to_string() + " " + &request_uri(request, false);
+    /* Keys show up in the debug output of the client, so the token is not
+     * part of them */
+    if let ClientType::Auth(c) = request.inner.client.inner.as_ref() {
+        key = key + " " + &token_digest(&c.credentials.access_token);
+    }
+    Some(key)
+}
+
+/* A lookup that found nothing may succeed moments later, e.g. for a clip that
+ * is still being created, so empty pages are not cached */
+fn is_empty_page(body: &Bytes) -> bool {
+    serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| value.get("data")?.as_array().map(Vec::is_empty))
+        .unwrap_or(false)
+}
+
+/// Drop cached GET responses of `endpoint` with any query, e.g. after a change
+/// made through `client`
+pub(crate) async fn invalidate_cache(client: &Client, endpoint: &str) {
+    if let Some(cache) = &client.config().cache {
+        let prefix = format!("{} {}{}?", Method::GET, client.api_base_uri(), endpoint);
+        cache.store().remove_prefix(&prefix).await;
+    }
+}
+
+/* Stands in for a token in keys. Stable across builds, unlike the std hashers,
+ * so keys persisted by a cache store remain valid */
+fn token_digest(token: &str) -> String {
+    Sha256::digest(token.as_bytes())[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Cache configuration, key and ttl when the response of `request` may be cached
+fn cache_entry<T, E>(request: &ApiRequest<T, E>) -> Option<(&CacheConfig, String, Duration)> {
+    let client = &request.inner.client;
+    let cache = client.config().cache.as_ref()?;
+    if !request.inner.cache || request.inner.method != Method::GET || request.inner.body.is_some() {
+        return None;
+    }
 
-        return res;
+    let endpoint = request.inner.url.strip_prefix(client.api_base_uri())?;
+    let ttl = cache.ttl(endpoint)?;
+    let mut key = request.inner.method.to_string() + " " + &request_uri(request, false);
+    /* Responses can depend on the token. The token itself is kept out of the
+     * key as the store may persist keys */
+    if let ClientType::Auth(c) = client.inner.as_ref() {
+        key = key + " " + &token_digest(&c.credentials.access_token);
     }
+    Some((cache, key, ttl))
 }
 
 impl<T, E, Opt> IntoFuture for RequestBuilder<T, E, Opt>
@@ -681,18 +1458,26 @@ where
     E: DeserializeOwned + 'static + Send,
 {
     type Output = Result<T, Error>;
-    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output>>>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        let request = self.build();
-        return Box::pin(perform_api_request(request));
+        let merge = match self.merge {
+            Some(merge) if self.batch.len() > MAX_BATCH_SIZE => merge,
+            _ => return Box::pin(perform_api_request(self.build())),
+        };
+
+        let requests = self.build_batches();
+        Box::pin(async move {
+            let parts = try_join_all(requests.into_iter().map(perform_api_request)).await?;
+            Ok(merge(parts))
+        })
     }
 }
 
 pub struct IterableApiRequest<T, E> {
     inner: Arc<RequestRef>,
     cursor: Option<String>,
-    _forward: bool,
+    forward: bool,
     _marker: PhantomData<T>,
     _error_type: PhantomData<E>,
 }
@@ -702,7 +1487,7 @@ impl<T, E> IterableApiRequest<T, E> {
         IterableApiRequest {
             inner: request.inner.clone(),
             cursor: None,
-            _forward: true,
+            forward: true,
             _marker: PhantomData,
             _error_type: PhantomData,
         }
@@ -715,11 +1500,83 @@ impl<T, E> IterableApiRequest<T, E> {
         IterableApiRequest {
             inner: request,
             cursor: cursor,
-            _forward: forward,
+            forward,
             _marker: PhantomData,
             _error_type: PhantomData,
         }
     }
+
+    /// Capture the position of this request so it can be resumed later,
+    /// possibly by a different process
+    pub fn checkpoint(&self) -> PaginationCheckpoint {
+        let base = self.inner.client.api_base_uri();
+        let endpoint = self.inner.url.strip_prefix(base).unwrap_or(&self.inner.url);
+
+        PaginationCheckpoint {
+            endpoint: endpoint.to_owned(),
+            method: self.inner.method.clone(),
+            params: self.inner.params.clone(),
+            cursor: self.cursor.clone(),
+            forward: self.forward,
+        }
+    }
+
+    /// Rebuild a request from a checkpoint obtained with `checkpoint`
+    pub fn from_checkpoint(client: Client, checkpoint: PaginationCheckpoint) -> Self {
+        let url = if checkpoint.endpoint.starts_with('/') {
+            client.api_base_uri().to_owned() + &checkpoint.endpoint
+        } else {
+            checkpoint.endpoint
+        };
+        let ratelimit = client
+            .ratelimit(RatelimitKey::Default)
+            .map(|m| m.to_owned());
+        let request = RequestRef::new(url, checkpoint.params, client, checkpoint.method, ratelimit);
+
+        IterableApiRequest::from_request_with_cursor(
+            Arc::new(request),
+            checkpoint.cursor,
+            checkpoint.forward,
+        )
+    }
+}
+
+/// Serializable position within a paginated endpoint.
+///
+/// The endpoint is stored relative to the api base uri so a checkpoint can be
+/// resumed by a client with a different configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaginationCheckpoint {
+    endpoint: String,
+    #[serde(with = "method_serde")]
+    method: Method,
+    params: Vec<(String, String)>,
+    cursor: Option<String>,
+    forward: bool,
+}
+
+impl PaginationCheckpoint {
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+}
+
+mod method_serde {
+    use hyper::Method;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(method: &Method, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(method.as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Method, D::Error> {
+        let method = String::deserialize(deserializer)?;
+        Method::from_bytes(method.as_bytes()).map_err(serde::de::Error::custom)
+    }
 }
 
 impl<
@@ -740,6 +1597,7 @@ impl<
             max_attempts,
             pagination: None,
             forward: true,
+            coalesce: true,
             _marker: PhantomData,
             _error_type: PhantomData,
         }
@@ -763,7 +1621,7 @@ impl<
         IterableApiRequest {
             inner: request_ref,
             cursor: None,
-            _forward: true,
+            forward: true,
             _marker: PhantomData,
             _error_type: PhantomData,
         }
@@ -776,9 +1634,14 @@ where
     E: serde::de::DeserializeOwned + Send,
 {
     let inner = request.inner.clone();
+    let cursor = request.pagination.clone();
+    let forward = request.forward;
     let mut res = perform_api_request(request).await?;
     res.set_base_request(inner);
-    res.set_last_direction(true);
+    res.set_last_direction(forward);
+    if let Some(cursor) = cursor {
+        res.set_last_cursor(cursor);
+    }
     Ok(res)
 }
 
@@ -788,7 +1651,7 @@ where
     E: serde::de::DeserializeOwned + Send + 'static,
 {
     type Output = Result<T, Error>;
-    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output>>>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
 
     fn into_future(self) -> Self::IntoFuture {
         let r = self.inner;
@@ -797,7 +1660,8 @@ where
             inner: r.clone(),
             max_attempts: 1,
             pagination: self.cursor,
-            forward: true,
+            forward: self.forward,
+            coalesce: true,
             _marker: PhantomData,
             _error_type: PhantomData,
         };
@@ -0,0 +1,260 @@
+//! Connector that optionally routes connections through an HTTP proxy
+//!
+//! Plain http requests are sent to the proxy in absolute form. Connections to
+//! https hosts are tunneled through the proxy with `CONNECT`, the TLS
+//! handshake is then performed by the wrapping `HttpsConnector`.
+//!
+//! The TLS backend is selected with the `native-tls` and `rustls-tls`
+//! features. Without either only plain http connections can be made.
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use hyper::client::connect::{Connected, Connection, HttpConnector};
+use hyper::service::Service;
+use hyper::Uri;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::net::TcpStream;
+
+use crate::error::Error;
+
+#[cfg(feature = "native-tls")]
+pub type Connector = hyper_tls::HttpsConnector<ProxyConnector>;
+
+#[cfg(all(feature = "rustls-tls", not(feature = "native-tls")))]
+pub type Connector = hyper_rustls::HttpsConnector<ProxyConnector>;
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls-tls")))]
+pub type Connector = ProxyConnector;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/* Upper bound on the size of a proxy's response to CONNECT */
+const MAX_CONNECT_RESPONSE: usize = 8 * 1024;
+
+/// Create the connector used by the http client.
+///
+/// `root_certificates` are PEM encoded certificates trusted in addition to
+/// the backend's default roots. With `http2_only` the connector asks servers
+/// for HTTP/2 during the TLS handshake
+#[cfg(feature = "native-tls")]
+pub fn build(
+    proxy: ProxyConnector,
+    root_certificates: &[Vec<u8>],
+    http2_only: bool,
+) -> Result<Connector, Error> {
+    let mut tls = native_tls::TlsConnector::builder();
+    /* hyper-tls does not pass the negotiated protocol on to hyper, which then
+     * speaks HTTP/1.1 unless it was told to use HTTP/2 only. Offering h2 in
+     * any other case would let servers pick a protocol that is not spoken */
+    if http2_only {
+        tls.request_alpns(&["h2"]);
+    }
+    for pem in root_certificates {
+        let certificate = native_tls::Certificate::from_pem(pem)
+            .map_err(|e| Error::config_error(format!("invalid root certificate: {}", e)))?;
+        tls.add_root_certificate(certificate);
+    }
+    let tls = tls
+        .build()
+        .map_err(|e| Error::config_error(format!("unable to initialize tls: {}", e)))?;
+
+    Ok(Connector::from((
+        proxy,
+        tokio_native_tls::TlsConnector::from(tls),
+    )))
+}
+
+/* hyper-rustls offers both protocols and reports the one that was chosen */
+#[cfg(all(feature = "rustls-tls", not(feature = "native-tls")))]
+pub fn build(
+    proxy: ProxyConnector,
+    root_certificates: &[Vec<u8>],
+    _http2_only: bool,
+) -> Result<Connector, Error> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+            ta.subject,
+            ta.spki,
+            ta.name_constraints,
+        )
+    }));
+
+    for pem in root_certificates {
+        let certificates = rustls_pemfile::certs(&mut pem.as_slice())
+            .map_err(|e| Error::config_error(format!("invalid root certificate: {}", e)))?;
+        if certificates.is_empty() {
+            return Err(Error::config_error(
+                "invalid root certificate: no certificate found",
+            ));
+        }
+        for der in certificates {
+            roots
+                .add(&rustls::Certificate(der))
+                .map_err(|e| Error::config_error(format!("invalid root certificate: {}", e)))?;
+        }
+    }
+
+    let tls = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    Ok(hyper_rustls::HttpsConnectorBuilder::new()
+        .with_tls_config(tls)
+        .https_or_http()
+        .enable_http1()
+        .enable_http2()
+        .wrap_connector(proxy))
+}
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls-tls")))]
+pub fn build(
+    proxy: ProxyConnector,
+    root_certificates: &[Vec<u8>],
+    _http2_only: bool,
+) -> Result<Connector, Error> {
+    if !root_certificates.is_empty() {
+        return Err(Error::config_error(
+            "root certificates require a tls backend feature",
+        ));
+    }
+    Ok(proxy)
+}
+
+#[derive(Clone, Debug)]
+pub struct ProxyConnector {
+    http: HttpConnector,
+    proxy: Option<Uri>,
+}
+
+impl ProxyConnector {
+    /// Without a tls backend only http uris are accepted, otherwise https
+    /// uris are passed on to be wrapped in tls
+    pub fn new(mut http: HttpConnector, proxy: Option<Uri>) -> ProxyConnector {
+        http.enforce_http(cfg!(not(any(
+            feature = "native-tls",
+            feature = "rustls-tls"
+        ))));
+        ProxyConnector { http, proxy }
+    }
+}
+
+impl Service<Uri> for ProxyConnector {
+    type Response = ProxyStream;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<ProxyStream, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let mut http = self.http.clone();
+        let proxy = self.proxy.clone();
+
+        Box::pin(async move {
+            let proxy = match proxy {
+                Some(proxy) => proxy,
+                None => {
+                    let stream = http.call(dst).await?;
+                    return Ok(ProxyStream {
+                        stream,
+                        proxied: false,
+                    });
+                }
+            };
+
+            let mut stream = http.call(proxy).await?;
+            if dst.scheme_str() != Some("https") {
+                return Ok(ProxyStream {
+                    stream,
+                    proxied: true,
+                });
+            }
+
+            tunnel(&mut stream, &dst).await?;
+            Ok(ProxyStream {
+                stream,
+                proxied: false,
+            })
+        })
+    }
+}
+
+async fn tunnel(stream: &mut TcpStream, dst: &Uri) -> Result<(), BoxError> {
+    let host = dst.host().ok_or("destination has no host")?;
+    let port = dst.port_u16().unwrap_or(443);
+    let connect = format!(
+        "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n\r\n",
+        host = host,
+        port = port
+    );
+    stream.write_all(connect.as_bytes()).await?;
+
+    let mut response = Vec::new();
+    let mut buf = [0; 1024];
+    while !response.ends_with(b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            return Err(
+                io::Error::new(io::ErrorKind::UnexpectedEof, "proxy closed connection").into(),
+            );
+        }
+        response.extend_from_slice(&buf[..n]);
+        if response.len() > MAX_CONNECT_RESPONSE {
+            return Err("proxy response too large".into());
+        }
+    }
+
+    if response.starts_with(b"HTTP/1.1 200") || response.starts_with(b"HTTP/1.0 200") {
+        Ok(())
+    } else {
+        let status = String::from_utf8_lossy(&response);
+        let status = status.lines().next().unwrap_or_default();
+        Err(format!("proxy refused tunnel: {}", status).into())
+    }
+}
+
+#[derive(Debug)]
+pub struct ProxyStream {
+    stream: TcpStream,
+    proxied: bool,
+}
+
+impl Connection for ProxyStream {
+    fn connected(&self) -> Connected {
+        self.stream.connected().proxy(self.proxied)
+    }
+}
+
+impl AsyncRead for ProxyStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for ProxyStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
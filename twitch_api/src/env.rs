@@ -0,0 +1,106 @@
+//! Configuration from environment variables
+//!
+//! Variables are read from the process environment. A `.env` file in the
+//! working directory or one of its parents is loaded first; variables that
+//! are already set take precedence over it.
+//!
+//! | Variable                  | Used for                                        |
+//! |---------------------------|-------------------------------------------------|
+//! | `TWITCH_CLIENT_ID`        | Client id. Required                             |
+//! | `TWITCH_CLIENT_SECRET`    | Client secret. Required unless a token is given |
+//! | `TWITCH_USER_TOKEN`       | User access token to use instead of an app token|
+//! | `TWITCH_REFRESH_TOKEN`    | Refresh token for the user access token         |
+//! | `TWITCH_SCOPES`           | Space separated scopes of the user access token |
+//! | `TWITCH_API_BASE_URI`     | Base uri of the Helix api                       |
+//! | `TWITCH_AUTH_BASE_URI`    | Base uri of the OAuth api                       |
+//! | `TWITCH_PROXY`            | Http proxy for all requests                     |
+//! | `TWITCH_TIMEOUT`          | Request timeout in seconds                      |
+//! | `TWITCH_READ_TIMEOUT`     | Read timeout in seconds                         |
+//! | `TWITCH_CONNECT_TIMEOUT`  | Connect timeout in seconds                      |
+//! | `TWITCH_MAX_RETRYS`       | Number of times a failed request is retried     |
+
+use std::convert::TryFrom;
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::client::{ClientConfigBuilder, HelixScope};
+use crate::error::Error;
+
+pub const CLIENT_ID: &str = "TWITCH_CLIENT_ID";
+pub const CLIENT_SECRET: &str = "TWITCH_CLIENT_SECRET";
+pub const USER_TOKEN: &str = "TWITCH_USER_TOKEN";
+pub const REFRESH_TOKEN: &str = "TWITCH_REFRESH_TOKEN";
+pub const SCOPES: &str = "TWITCH_SCOPES";
+pub const API_BASE_URI: &str = "TWITCH_API_BASE_URI";
+pub const AUTH_BASE_URI: &str = "TWITCH_AUTH_BASE_URI";
+pub const PROXY: &str = "TWITCH_PROXY";
+pub const TIMEOUT: &str = "TWITCH_TIMEOUT";
+pub const READ_TIMEOUT: &str = "TWITCH_READ_TIMEOUT";
+pub const CONNECT_TIMEOUT: &str = "TWITCH_CONNECT_TIMEOUT";
+pub const MAX_RETRYS: &str = "TWITCH_MAX_RETRYS";
+
+/// Load `.env` if present. Existing variables are not overwritten
+pub(crate) fn load_dotenv() {
+    dotenv::dotenv().ok();
+}
+
+/// Value of a variable. Unset and empty variables are treated the same
+pub(crate) fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+pub(crate) fn required(name: &str) -> Result<String, Error> {
+    var(name).ok_or_else(|| Error::config_error(format!("{} is not set", name)))
+}
+
+fn parse<T: FromStr>(name: &str) -> Result<Option<T>, Error>
+where
+    T::Err: std::fmt::Display,
+{
+    match var(name) {
+        Some(value) => value
+            .parse::<T>()
+            .map(Some)
+            .map_err(|e| Error::config_error(format!("invalid {}: {}", name, e))),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn scopes() -> Result<Vec<HelixScope>, Error> {
+    var(SCOPES)
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|scope| {
+            HelixScope::try_from(scope)
+                .map_err(|_| Error::config_error(format!("invalid {}: {}", SCOPES, scope)))
+        })
+        .collect()
+}
+
+/// Apply the variables that affect the client configuration
+pub(crate) fn configure(mut builder: ClientConfigBuilder) -> Result<ClientConfigBuilder, Error> {
+    if let Some(uri) = var(API_BASE_URI) {
+        builder = builder.api_base_uri(uri);
+    }
+    if let Some(uri) = var(AUTH_BASE_URI) {
+        builder = builder.auth_base_uri(uri);
+    }
+    if let Some(uri) = var(PROXY) {
+        builder = builder.proxy(uri);
+    }
+    if let Some(secs) = parse::<u64>(TIMEOUT)? {
+        builder = builder.timeout(Duration::from_secs(secs));
+    }
+    if let Some(secs) = parse::<u64>(READ_TIMEOUT)? {
+        builder = builder.read_timeout(Duration::from_secs(secs));
+    }
+    if let Some(secs) = parse::<u64>(CONNECT_TIMEOUT)? {
+        builder = builder.connect_timeout(Duration::from_secs(secs));
+    }
+    if let Some(retrys) = parse::<u32>(MAX_RETRYS)? {
+        builder = builder.max_retrys(retrys);
+    }
+
+    Ok(builder)
+}
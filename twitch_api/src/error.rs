@@ -1,15 +1,20 @@
-use crate::helix::models::ApiError;
+use crate::client::HelixScope;
+use crate::helix::models::{ApiError, ContentClassificationLabel};
 use crate::models::Message;
+use hyper::http::Error as HttpError;
 use hyper::Error as HyperError;
 use serde_json::Error as JsonError;
 use std::convert::From;
 use std::error::Error as StdError;
 use std::fmt::Display;
+use std::sync::Arc;
 use tokio::time::error::Elapsed;
 
 #[derive(Debug)]
 pub(crate) enum Kind {
     Hyper(HyperError),
+    /// The request could not be built, e.g. because of an invalid uri
+    Http(HttpError),
     Io(std::io::Error),
     Json(JsonError),
     AuthError(Option<Message>),
@@ -17,6 +22,55 @@ pub(crate) enum Kind {
     RatelimitCostError(String),
     GeneralApiError(ApiError),
     Timeout(Elapsed),
+    /// Failure of a request whose response was shared by several callers
+    Shared(Arc<Error>),
+    Config(String),
+    Validation(ValidationError),
+    /// The token under the key of a token store can no longer be used
+    StoredTokenRejected(String),
+}
+
+/// Reason a request was rejected before it was sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The token of the client lacks a scope the endpoint requires
+    MissingScope(HelixScope),
+    /// The request would not change anything
+    NoChanges,
+    EmptyTitle,
+    /// Length of the title in characters
+    TitleTooLong(usize),
+    /// Delay in seconds
+    DelayTooLong(u32),
+    /// Number of tags
+    TooManyTags(usize),
+    InvalidTag(String),
+    InvalidLanguage(String),
+    /// The label is managed by Twitch
+    ReadOnlyLabel(ContentClassificationLabel),
+    /// Length of the description in characters
+    DescriptionTooLong(usize),
+    /// The endpoint requires the stream to be live
+    NotLive,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use self::ValidationError::*;
+        match self {
+            MissingScope(scope) => write!(f, "missing scope {}", scope.to_str()),
+            NoChanges => write!(f, "nothing to update"),
+            EmptyTitle => write!(f, "title must not be empty"),
+            TitleTooLong(len) => write!(f, "title is {} characters long", len),
+            DelayTooLong(delay) => write!(f, "delay of {} seconds is too long", delay),
+            TooManyTags(count) => write!(f, "{} tags are too many", count),
+            InvalidTag(tag) => write!(f, "invalid tag {:?}", tag),
+            InvalidLanguage(language) => write!(f, "invalid language {:?}", language),
+            ReadOnlyLabel(label) => write!(f, "label {:?} cannot be changed", label),
+            DescriptionTooLong(len) => write!(f, "description is {} characters long", len),
+            NotLive => write!(f, "stream is not live"),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -33,6 +87,18 @@ impl Display for Error {
             )?;
         } else if self.is_ratelimit_error() {
             write!(f, "Twitch ratelimit hit. Try your request again")?;
+        } else if let Kind::Config(message) = &self.inner {
+            write!(f, "Invalid client configuration: {}", message)?;
+        } else if let Kind::Validation(err) = &self.inner {
+            write!(f, "Invalid request: {}", err)?;
+        } else if let Kind::Http(err) = &self.inner {
+            write!(f, "Invalid request: {}", err)?;
+        } else if let Kind::StoredTokenRejected(key) = &self.inner {
+            write!(
+                f,
+                "The token stored under {} was rejected and could not be refreshed",
+                key
+            )?;
         } else {
             write!(f, "Unable to perform Twitch API request")?;
         }
@@ -45,6 +111,7 @@ impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match &self.inner {
             Kind::Hyper(e) => e.source(),
+            Kind::Http(e) => Some(e),
             Kind::Io(e) => e.source(),
             Kind::Json(e) => e.source(),
             Kind::AuthError(_) => None,
@@ -52,6 +119,10 @@ impl StdError for Error {
             Kind::RatelimitCostError(_) => None,
             Kind::GeneralApiError(_) => None,
             Kind::Timeout(e) => e.source(),
+            Kind::Shared(e) => e.source(),
+            Kind::Config(_) => None,
+            Kind::Validation(_) => None,
+            Kind::StoredTokenRejected(_) => None,
         }
     }
 }
@@ -69,6 +140,18 @@ impl Error {
         }
     }
 
+    pub fn config_error<S: Into<String>>(message: S) -> Error {
+        Error {
+            inner: Kind::Config(message.into()),
+        }
+    }
+
+    pub(crate) fn stored_token_rejected(key: &str) -> Error {
+        Error {
+            inner: Kind::StoredTokenRejected(key.to_owned()),
+        }
+    }
+
     pub fn is_auth_error(&self) -> bool {
         match &self.inner {
             Kind::AuthError(_) => true,
@@ -83,6 +166,32 @@ impl Error {
         }
     }
 
+    /* OAuth rejects invalid, expired and revoked tokens with 400 or 401 */
+    pub(crate) fn is_token_rejected(&self) -> bool {
+        self.is_auth_error() || self.get_api_error().map(|e| e.status) == Some(401)
+    }
+
+    /// The token of a token store was deleted as it can no longer be used.
+    /// See `AuthClientBuilder::token_store`
+    pub fn is_stored_token_rejected(&self) -> bool {
+        matches!(&self.inner, Kind::StoredTokenRejected(_))
+    }
+
+    pub fn is_config_error(&self) -> bool {
+        matches!(&self.inner, Kind::Config(_))
+    }
+
+    pub fn is_validation_error(&self) -> bool {
+        matches!(&self.inner, Kind::Validation(_))
+    }
+
+    pub fn get_validation_error(&self) -> Option<&ValidationError> {
+        match &self.inner {
+            Kind::Validation(err) => Some(err),
+            _ => None,
+        }
+    }
+
     pub fn get_api_error(&self) -> Option<&ApiError> {
         match &self.inner {
             Kind::GeneralApiError(err) => Some(err),
@@ -99,6 +208,14 @@ impl From<HyperError> for Error {
     }
 }
 
+impl From<HttpError> for Error {
+    fn from(err: HttpError) -> Error {
+        Error {
+            inner: Kind::Http(err),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Error {
@@ -123,6 +240,25 @@ impl From<Elapsed> for Error {
     }
 }
 
+impl From<Arc<Error>> for Error {
+    fn from(err: Arc<Error>) -> Error {
+        match Arc::try_unwrap(err) {
+            Ok(err) => err,
+            Err(err) => Error {
+                inner: Kind::Shared(err),
+            },
+        }
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(err: ValidationError) -> Error {
+        Error {
+            inner: Kind::Validation(err),
+        }
+    }
+}
+
 impl From<ApiError> for Error {
     fn from(err: ApiError) -> Error {
         if err.status == 400 {
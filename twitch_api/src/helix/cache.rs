@@ -0,0 +1,252 @@
+use futures::future::{ready, BoxFuture};
+use hyper::body::Bytes;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Storage for raw response bodies.
+///
+/// Implement this to keep responses somewhere other than process memory.
+/// Entries must not be returned once their ttl has passed.
+pub trait ResponseCache: Send + Sync {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Bytes>>;
+    fn put(&self, key: String, value: Bytes, ttl: Duration) -> BoxFuture<'_, ()>;
+    /// Remove all entries whose key starts with `prefix`. Used to drop
+    /// responses that are outdated after a change, e.g. a channel update
+    fn remove_prefix<'a>(&'a self, prefix: &'a str) -> BoxFuture<'a, ()>;
+}
+
+/// Enables response caching for GET requests.
+///
+/// Only endpoints with a ttl are cached. Endpoints are given relative to the
+/// api base uri, i.e. "/users"
+#[derive(Clone)]
+pub struct CacheConfig {
+    store: Arc<dyn ResponseCache>,
+    ttls: HashMap<String, Duration>,
+}
+
+impl CacheConfig {
+    /// Cache using `store` with the default ttls. Users and games are kept for
+    /// an hour, channels, videos and clips for five minutes and streams for
+    /// thirty seconds
+    pub fn new<C: ResponseCache + 'static>(store: C) -> CacheConfig {
+        let mut ttls = HashMap::new();
+        ttls.insert("/users".to_owned(), Duration::from_secs(60 * 60));
+        ttls.insert("/games".to_owned(), Duration::from_secs(60 * 60));
+        ttls.insert("/channels".to_owned(), Duration::from_secs(5 * 60));
+        ttls.insert("/videos".to_owned(), Duration::from_secs(5 * 60));
+        ttls.insert("/clips".to_owned(), Duration::from_secs(5 * 60));
+        ttls.insert("/streams".to_owned(), Duration::from_secs(30));
+
+        CacheConfig {
+            store: Arc::new(store),
+            ttls,
+        }
+    }
+
+    /// Cache up to `capacity` responses in memory
+    pub fn in_memory(capacity: usize) -> CacheConfig {
+        CacheConfig::new(MemoryCache::new(capacity))
+    }
+
+    /// Set the ttl for an endpoint. A ttl of zero disables caching for it
+    pub fn with_ttl<S: Into<String>>(mut self, endpoint: S, ttl: Duration) -> Self {
+        self.ttls.insert(endpoint.into(), ttl);
+        self
+    }
+
+    pub fn ttl(&self, endpoint: &str) -> Option<Duration> {
+        self.ttls
+            .get(endpoint)
+            .cloned()
+            .filter(|ttl| !ttl.is_zero())
+    }
+
+    pub fn store(&self) -> &dyn ResponseCache {
+        self.store.as_ref()
+    }
+}
+
+impl fmt::Debug for CacheConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CacheConfig")
+            .field("ttls", &self.ttls)
+            .finish()
+    }
+}
+
+/// Least recently used in-memory cache
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    inner: Mutex<MemoryCacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryCacheInner {
+    tick: u64,
+    entries: HashMap<String, MemoryEntry>,
+    /* Last use of each entry. The first entry is evicted when full */
+    usage: BTreeMap<u64, String>,
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+    value: Bytes,
+    expires: Instant,
+    used: u64,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> MemoryCache {
+        MemoryCache {
+            capacity,
+            inner: Mutex::new(MemoryCacheInner::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lookup(&self, key: &str) -> Option<Bytes> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        let entry = inner.entries.get_mut(key)?;
+        if entry.expires <= Instant::now() {
+            let used = entry.used;
+            inner.entries.remove(key);
+            inner.usage.remove(&used);
+            return None;
+        }
+
+        let previous = std::mem::replace(&mut entry.used, tick);
+        let value = entry.value.clone();
+        inner.usage.remove(&previous);
+        inner.usage.insert(tick, key.to_owned());
+        Some(value)
+    }
+
+    fn remove(&self, prefix: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        let MemoryCacheInner { entries, usage, .. } = &mut *inner;
+        entries.retain(|key, entry| {
+            let keep = !key.starts_with(prefix);
+            if !keep {
+                usage.remove(&entry.used);
+            }
+            keep
+        });
+    }
+
+    fn insert(&self, key: String, value: Bytes, ttl: Duration) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.tick += 1;
+        let tick = inner.tick;
+
+        if let Some(entry) = inner.entries.remove(&key) {
+            inner.usage.remove(&entry.used);
+        }
+
+        while inner.entries.len() >= self.capacity {
+            let oldest = *inner.usage.keys().next().unwrap();
+            let evicted = inner.usage.remove(&oldest).unwrap();
+            inner.entries.remove(&evicted);
+        }
+
+        inner.usage.insert(tick, key.clone());
+        inner.entries.insert(
+            key,
+            MemoryEntry {
+                value,
+                expires: Instant::now() + ttl,
+                used: tick,
+            },
+        );
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Bytes>> {
+        Box::pin(ready(self.lookup(key)))
+    }
+
+    fn put(&self, key: String, value: Bytes, ttl: Duration) -> BoxFuture<'_, ()> {
+        self.insert(key, value, ttl);
+        Box::pin(ready(()))
+    }
+
+    fn remove_prefix<'a>(&'a self, prefix: &'a str) -> BoxFuture<'a, ()> {
+        self.remove(prefix);
+        Box::pin(ready(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::new(2);
+        let ttl = Duration::from_secs(60);
+
+        cache.put("a".to_owned(), Bytes::from("1"), ttl).await;
+        cache.put("b".to_owned(), Bytes::from("2"), ttl).await;
+        assert_eq!(cache.get("a").await, Some(Bytes::from("1")));
+
+        cache.put("c".to_owned(), Bytes::from("3"), ttl).await;
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b").await, None);
+        assert_eq!(cache.get("a").await, Some(Bytes::from("1")));
+        assert_eq!(cache.get("c").await, Some(Bytes::from("3")));
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_remove_prefix() {
+        let cache = MemoryCache::new(4);
+        let ttl = Duration::from_secs(60);
+
+        cache
+            .put("GET /a?id=1".to_owned(), Bytes::from("1"), ttl)
+            .await;
+        cache
+            .put("GET /a?id=2 token".to_owned(), Bytes::from("2"), ttl)
+            .await;
+        cache
+            .put("GET /a/b?id=1".to_owned(), Bytes::from("3"), ttl)
+            .await;
+
+        cache.remove_prefix("GET /a?").await;
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("GET /a/b?id=1").await, Some(Bytes::from("3")));
+
+        cache
+            .put("GET /a?id=1".to_owned(), Bytes::from("4"), ttl)
+            .await;
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_expires() {
+        let cache = MemoryCache::new(2);
+
+        cache
+            .put("a".to_owned(), Bytes::from("1"), Duration::from_millis(0))
+            .await;
+        assert_eq!(cache.get("a").await, None);
+        assert!(cache.is_empty());
+    }
+}
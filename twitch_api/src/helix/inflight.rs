@@ -0,0 +1,74 @@
+use crate::error::Error;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use hyper::body::Bytes;
+use hyper::StatusCode;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+pub(crate) type SharedResponse = Result<(StatusCode, Bytes), Arc<Error>>;
+type InflightFuture = Shared<BoxFuture<'static, SharedResponse>>;
+
+/// Requests currently being performed, keyed by method, uri and token.
+///
+/// Callers that issue a request identical to one already in flight wait for
+/// its response instead of sending their own.
+#[derive(Default, Clone)]
+pub(crate) struct InflightRequests {
+    inner: Arc<Mutex<HashMap<String, InflightFuture>>>,
+}
+
+impl std::fmt::Debug for InflightRequests {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let inflight = self.inner.lock().unwrap();
+        f.debug_struct("InflightRequests")
+            .field("keys", &inflight.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl InflightRequests {
+    /// Await the request in flight for `key` or start one with `fetch`
+    pub(crate) async fn run<F>(&self, key: String, fetch: F) -> SharedResponse
+    where
+        F: Future<Output = Result<(StatusCode, Bytes), Error>> + Send + 'static,
+    {
+        let shared = {
+            let mut inflight = self.inner.lock().unwrap();
+            match inflight.get(&key) {
+                Some(shared) => {
+                    trace!("joining in-flight request {}", key);
+                    shared.clone()
+                }
+                None => {
+                    /* The fetch runs as its own task so it completes and
+                     * leaves the map even when every caller stops waiting.
+                     * The entry is removed after it was inserted below, as
+                     * the task has to take the lock first */
+                    let registry = self.inner.clone();
+                    let cleanup_key = key.clone();
+                    let task = tokio::spawn(async move {
+                        let res = fetch.await.map_err(Arc::new);
+                        registry.lock().unwrap().remove(&cleanup_key);
+                        res
+                    });
+                    let shared = async move {
+                        match task.await {
+                            Ok(res) => res,
+                            Err(e) if e.is_panic() => panic::resume_unwind(e.into_panic()),
+                            Err(e) => Err(Arc::new(io::Error::from(e).into())),
+                        }
+                    }
+                    .boxed()
+                    .shared();
+                    inflight.insert(key, shared.clone());
+                    shared
+                }
+            }
+        };
+
+        shared.await
+    }
+}
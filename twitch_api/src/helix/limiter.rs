@@ -1,5 +1,8 @@
+use crate::client::RatelimitKey;
 use crate::error::{Error, Kind};
 use hyper::HeaderMap;
+use std::collections::HashMap;
+use std::sync::Mutex;
 use std::{
     sync::atomic::{AtomicI32, AtomicI64, Ordering},
     time::SystemTime,
@@ -47,6 +50,56 @@ impl BucketLimiter {
 
         BucketLimiter(Arc::new(bucket))
     }
+
+    /* Whether the bucket is full again and has no requests in flight */
+    fn is_reset(&self) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        self.0.inflight.load(Ordering::Relaxed) == 0 && self.0.reset.load(Ordering::Relaxed) <= now
+    }
+
+    /// A new, full bucket with the same limit and headers as this one
+    pub fn fresh(&self) -> BucketLimiter {
+        BucketLimiter::new(
+            self.0.limit.load(Ordering::Relaxed) as u32,
+            &self.0.limit_header,
+            &self.0.remaining_header,
+            &self.0.reset_header,
+        )
+    }
+}
+
+/// Buckets of users. Twitch limits each user separately, so every user gets
+/// their own buckets, created from a template on first use.
+///
+/// Users are identified by their id or a digest of their token. Buckets that
+/// no client holds are dropped once their limit has reset, as they are no
+/// different from a new bucket by then
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TokenBuckets {
+    inner: Arc<Mutex<HashMap<(RatelimitKey, String), BucketLimiter>>>,
+}
+
+impl TokenBuckets {
+    pub(crate) fn get(
+        &self,
+        owner: &str,
+        key: RatelimitKey,
+        template: &BucketLimiter,
+    ) -> BucketLimiter {
+        let mut buckets = self.inner.lock().unwrap();
+        let key = (key, owner.to_owned());
+        if let Some(bucket) = buckets.get(&key) {
+            return bucket.clone();
+        }
+
+        buckets.retain(|_, bucket| Arc::strong_count(&bucket.0) > 1 || !bucket.is_reset());
+        let bucket = template.fresh();
+        buckets.insert(key, bucket.clone());
+        bucket
+    }
 }
 
 impl BucketLimiter {
@@ -122,3 +175,37 @@ impl BucketLimiter {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_token_buckets_are_shared_and_dropped() {
+        let buckets = TokenBuckets::default();
+        let template = BucketLimiter::new(800, &"limit", &"remaining", &"reset");
+
+        let first = buckets.get("user 1", RatelimitKey::Default, &template);
+        let second = buckets.get("user 1", RatelimitKey::Default, &template);
+        assert!(Arc::ptr_eq(&first.0, &second.0));
+        let limited = buckets.get("user 2", RatelimitKey::Default, &template);
+        limited.0.reset.store(i64::MAX, Ordering::Relaxed);
+        drop(limited);
+
+        /* Buckets in use or waiting for a reset are kept */
+        buckets.get("user 3", RatelimitKey::Default, &template);
+        assert_eq!(buckets.inner.lock().unwrap().len(), 3);
+
+        drop((first, second));
+        buckets.get("user 4", RatelimitKey::Default, &template);
+        let mut owners: Vec<_> = buckets
+            .inner
+            .lock()
+            .unwrap()
+            .keys()
+            .map(|(_, owner)| owner.clone())
+            .collect();
+        owners.sort();
+        assert_eq!(owners, ["user 2", "user 4"]);
+    }
+}
@@ -3,10 +3,18 @@ use crate::client::ClientConfig;
 use crate::client::ClientTrait;
 
 use crate::client::HelixScope;
+use crate::client::{IterableApiRequest, PaginationCheckpoint};
 
+use self::models::ApiError;
+
+pub mod cache;
+pub(crate) mod inflight;
 pub mod limiter;
 pub mod models;
 pub mod namespaces;
+pub mod raw;
+pub mod token_store;
+pub mod tokens;
 
 #[derive(Clone, Debug)]
 pub struct Client {
@@ -27,10 +35,47 @@ impl Client {
         }
     }
 
+    /// Create an authenticated client from environment variables.
+    ///
+    /// With `TWITCH_USER_TOKEN` set the client uses that token, otherwise an
+    /// app access token is obtained with the client secret. See the `env`
+    /// module for all variables that are read
+    pub async fn from_env() -> Result<Client, Error> {
+        let config = ClientConfig::from_env()?;
+        let id = env::required(env::CLIENT_ID)?;
+        let token = env::var(env::USER_TOKEN);
+        let secret = match token {
+            Some(_) => env::var(env::CLIENT_SECRET).unwrap_or_default(),
+            None => env::required(env::CLIENT_SECRET)?,
+        };
+
+        let mut builder = Client::new_with_config(id, config)
+            .authenticate(secret)
+            .scopes(env::scopes()?);
+        if let Some(token) = token {
+            builder = builder.token(&token);
+        }
+        if let Some(refresh_token) = env::var(env::REFRESH_TOKEN) {
+            builder = builder.refresh_token(&refresh_token);
+        }
+
+        builder.build().await
+    }
+
     pub fn authenticate<S: Into<String>>(self, secret: S) -> AuthClientBuilder {
         AuthClientBuilder::new(self, secret)
     }
 
+    /// A handle that performs requests with the user access `token`.
+    ///
+    /// Handles share the connection pool and configuration of this client.
+    /// Each token is ratelimited separately
+    pub fn as_user(&self, token: &str) -> Client {
+        Client {
+            inner: self.inner.as_user(token),
+        }
+    }
+
     pub fn id<'a>(&'a self) -> &'a str {
         &self.inner.id()
     }
@@ -47,10 +92,20 @@ impl Client {
     pub fn scopes(&self) -> &[HelixScope] {
         self.inner.scopes()
     }
+
+    /// Continue a paginated request from a previously saved checkpoint
+    ///
+    /// The response type must match the endpoint the checkpoint was taken from
+    pub fn resume<T>(&self, checkpoint: PaginationCheckpoint) -> IterableApiRequest<T, ApiError> {
+        IterableApiRequest::from_checkpoint(self.inner.clone(), checkpoint)
+    }
 }
 
+use self::token_store::TokenStore;
 use crate::client::AuthClientBuilder as GenericAuthClientBuilder;
+use crate::env;
 use crate::error::Error;
+use std::sync::Arc;
 
 pub struct AuthClientBuilder {
     inner: GenericAuthClientBuilder,
@@ -85,4 +140,22 @@ impl AuthClientBuilder {
             inner: self.inner.token(token),
         }
     }
+
+    pub fn refresh_token(self, token: &str) -> AuthClientBuilder {
+        AuthClientBuilder {
+            inner: self.inner.refresh_token(token),
+        }
+    }
+
+    /// Keep the token in `store` under `key`. See
+    /// `client::AuthClientBuilder::token_store`
+    pub fn token_store<S: Into<String>>(
+        self,
+        store: Arc<dyn TokenStore>,
+        key: S,
+    ) -> AuthClientBuilder {
+        AuthClientBuilder {
+            inner: self.inner.token_store(store, key),
+        }
+    }
 }
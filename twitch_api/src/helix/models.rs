@@ -3,13 +3,15 @@ extern crate serde_json;
 
 use super::namespaces::IterableApiRequest;
 use crate::client::{
-    BidirectionalPagination, ForwardPagination, HelixPagination, PaginationContrainerTrait,
-    RequestRef,
+    BatchedResponse, BidirectionalPagination, ForwardPagination, HelixPagination, HelixScope,
+    PaginationCheckpoint, PaginationContrainerTrait, RequestRef,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer};
+use std::convert::TryFrom;
 use std::sync::Arc;
-use twitch_types::{BroadcasterId, GameId, StreamId, UserId, VideoId};
+use std::time::Duration;
+use twitch_types::{BroadcasterId, ClipId, GameId, StreamId, UserId, VideoId};
 use url::Url;
 
 fn null_as_empty<'de, D, T>(de: D) -> Result<Vec<T>, D::Error>
@@ -24,6 +26,56 @@ where
     }
 }
 
+/* Helix sends an empty string for unset ids and names */
+fn empty_as_none_string<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<String>,
+{
+    let value: Option<String> = Option::deserialize(de)?;
+    Ok(value.filter(|value| !value.is_empty()).map(T::from))
+}
+
+/* Labels that were added to Twitch after this version of the crate are
+ * dropped like unknown scopes */
+fn known_labels<'de, D>(de: D) -> Result<Vec<ContentClassificationLabel>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let labels: Vec<serde_json::Value> = null_as_empty(de)?;
+    Ok(labels
+        .into_iter()
+        .filter_map(|label| serde_json::from_value(label).ok())
+        .collect())
+}
+
+/* Helix sends an empty string instead of null for some unset timestamps */
+fn empty_as_none<'de, D>(de: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let time: Option<String> = Option::deserialize(de)?;
+    match time.as_deref() {
+        None | Some("") => Ok(None),
+        Some(time) => DateTime::parse_from_rfc3339(time)
+            .map(|time| Some(time.with_timezone(&Utc)))
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+/* Scopes that were added to Twitch after this version of the crate are
+ * dropped rather than failing the whole response */
+fn known_scopes<'de, D>(de: D) -> Result<Vec<HelixScope>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let scopes: Vec<String> = null_as_empty(de)?;
+    Ok(scopes
+        .iter()
+        .filter_map(|scope| HelixScope::try_from(scope.as_str()).ok())
+        .collect())
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct DataContainer<T> {
     pub data: Vec<T>,
@@ -35,65 +87,119 @@ impl<T> ForwardPagination for DataContainer<T> {
     }
 }
 
-impl<T> ForwardPagination for PaginationContainer<T> {
-    fn cursor<'a>(&'a self) -> Option<&'a str> {
-        match self.pagination.as_ref() {
-            Some(cursor) => match cursor.cursor.as_ref() {
-                Some(cursor) => Some(cursor),
-                None => None,
-            },
-            None => None,
+impl<T> BatchedResponse for DataContainer<T> {
+    fn merge(parts: Vec<Self>) -> Self {
+        DataContainer {
+            data: parts.into_iter().flat_map(|part| part.data).collect(),
         }
     }
 }
 
-impl<T> HelixPagination for PaginationContainer<T> {}
-
-impl<T> BidirectionalPagination<PaginationContainer<T>, ApiError> for PaginationContainer<T> {
-    fn next(
-        &self,
-    ) -> Option<super::namespaces::IterableApiRequest<PaginationContainer<T>, ApiError>> {
-        match self.cursor() {
-            Some(cursor) => Some(IterableApiRequest::from_request_with_cursor(
-                self.base_request.as_ref().unwrap().clone(),
-                Some(cursor.to_owned()),
-                true,
-            )),
-            None => None,
+/* The cursors of separate batches can't be combined, so a merged response is
+ * a single page */
+impl<T> BatchedResponse for PaginationContainer<T> {
+    fn merge(parts: Vec<Self>) -> Self {
+        PaginationContainer {
+            data: parts.into_iter().flat_map(|part| part.data).collect(),
+            pagination: None,
+            total: None,
+            position: PagePosition::default(),
         }
     }
+}
 
-    fn prev(
+/// Where a page was fetched from, set by the request that fetched it
+#[derive(Debug, Default)]
+struct PagePosition {
+    last_cursor: Option<String>,
+    last_direction: Option<bool>,
+    base_request: Option<Arc<RequestRef>>,
+}
+
+/* Helix returns a single cursor per page. A page obtained by moving forward
+ * has a cursor pointing past its last item and the page before it ends at the
+ * cursor that was used to request it. The reverse holds for pages obtained
+ * by moving backwards.
+ */
+impl PagePosition {
+    fn adjacent<T>(
         &self,
-    ) -> Option<super::namespaces::IterableApiRequest<PaginationContainer<T>, ApiError>> {
-        match self.cursor() {
-            Some(cursor) => Some(IterableApiRequest::from_request_with_cursor(
-                self.base_request.as_ref().unwrap().clone(),
-                Some(cursor.to_owned()),
-                false,
-            )),
-            None => None,
-        }
+        cursor: Option<&str>,
+        forward: bool,
+    ) -> Option<IterableApiRequest<T, ApiError>> {
+        let moved_forward = self.last_direction != Some(false);
+        let cursor = if forward == moved_forward {
+            cursor
+        } else {
+            self.last_cursor.as_deref()
+        };
+
+        Some(IterableApiRequest::from_request_with_cursor(
+            self.base_request.as_ref()?.clone(),
+            Some(cursor?.to_owned()),
+            forward,
+        ))
     }
 }
 
+/* Pagination of a Helix page type with the fields `pagination` and `position`.
+ * Generic parameters of the type are given in brackets */
+macro_rules! helix_page {
+    ([$($param:ident),*] $page:ty) => {
+        impl<$($param),*> ForwardPagination for $page {
+            fn cursor(&self) -> Option<&str> {
+                self.pagination.as_ref()?.cursor.as_deref()
+            }
+        }
+
+        impl<$($param),*> HelixPagination for $page {}
+
+        impl<$($param),*> BidirectionalPagination<$page, ApiError> for $page {
+            fn next(&self) -> Option<IterableApiRequest<$page, ApiError>> {
+                self.position.adjacent(self.cursor(), true)
+            }
+
+            fn prev(&self) -> Option<IterableApiRequest<$page, ApiError>> {
+                self.position.adjacent(self.cursor(), false)
+            }
+        }
+
+        impl<$($param),*> PaginationContrainerTrait for $page {
+            fn set_last_direction(&mut self, forward: bool) {
+                self.position.last_direction = Some(forward);
+            }
+
+            fn set_last_cursor(&mut self, cursor: String) {
+                self.position.last_cursor = Some(cursor);
+            }
+
+            fn set_base_request(&mut self, request: Arc<RequestRef>) {
+                self.position.base_request = Some(request);
+            }
+        }
+
+        impl<$($param),*> $page {
+            /// Checkpoint for the page after this one.
+            ///
+            /// Returns None when there are no further pages
+            pub fn checkpoint(&self) -> Option<PaginationCheckpoint> {
+                self.next().map(|request| request.checkpoint())
+            }
+        }
+    };
+}
+
+helix_page!([T] PaginationContainer<T>);
+
 impl ForwardPagination for Credentials {
     fn cursor<'a>(&'a self) -> Option<&'a str> {
         None
     }
 }
 
-impl<T> PaginationContrainerTrait for PaginationContainer<T> {
-    fn set_last_direction(&mut self, forward: bool) {
-        self.last_direction = Some(forward);
-    }
-
-    fn set_last_cursor(&mut self, cursor: String) {
-        self.last_cursor = Some(cursor);
-    }
-
-    fn set_base_request(&mut self, request: std::sync::Arc<RequestRef>) {
-        self.base_request = Some(request);
+impl ForwardPagination for TokenValidation {
+    fn cursor<'a>(&'a self) -> Option<&'a str> {
+        None
     }
 }
 
@@ -101,13 +207,12 @@ impl<T> PaginationContrainerTrait for PaginationContainer<T> {
 pub struct PaginationContainer<T> {
     pub data: Vec<T>,
     pub pagination: Option<Cursor>,
+    /// Total number of items across all pages, for endpoints that report it
+    #[serde(default)]
+    pub total: Option<u64>,
 
     #[serde(skip)]
-    last_cursor: Option<String>,
-    #[serde(skip)]
-    last_direction: Option<bool>,
-    #[serde(skip)]
-    base_request: Option<Arc<RequestRef>>,
+    position: PagePosition,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -168,7 +273,7 @@ pub struct User {
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Clip {
-    pub id: String,
+    pub id: ClipId<'static>,
     #[serde(with = "url_serde")]
     pub url: Url,
     #[serde(with = "url_serde")]
@@ -178,7 +283,7 @@ pub struct Clip {
     pub creator_id: UserId<'static>,
     pub creator_name: String,
     pub video_id: VideoId<'static>,
-    pub game_id: String,
+    pub game_id: GameId<'static>,
     pub language: String,
     pub title: String,
     pub created_at: DateTime<Utc>,
@@ -187,19 +292,70 @@ pub struct Clip {
     pub view_count: i32,
     pub duration: f32,
     pub vod_offset: Option<i32>,
+    /// Whether the broadcaster featured the clip
+    pub is_featured: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// A clip that is being created. See `clips().wait_for_clip`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CreatedClip {
+    pub id: ClipId<'static>,
+    #[serde(with = "url_serde")]
+    pub edit_url: Url,
+}
+
+/// An OAuth access token, as returned by the token endpoint
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Credentials {
     pub access_token: String,
     pub refresh_token: Option<String>,
+    /// Lifetime in seconds, counted from `obtained_at`. Zero when unknown
     pub expires_in: u32,
+    /// Twitch doesn't report when a token was issued, so this is the time the
+    /// token was received unless it was restored from a serialized copy
+    #[serde(default = "Utc::now")]
+    pub obtained_at: DateTime<Utc>,
     #[serde(default)]
-    #[serde(deserialize_with = "null_as_empty")]
-    pub scope: Vec<String>,
+    #[serde(deserialize_with = "known_scopes")]
+    pub scope: Vec<HelixScope>,
     pub token_type: String,
 }
 
+impl Credentials {
+    /// Credentials for a bearer token obtained now, of unknown lifetime
+    pub fn new<S: Into<String>>(access_token: S, refresh_token: Option<String>) -> Credentials {
+        Credentials {
+            access_token: access_token.into(),
+            refresh_token,
+            expires_in: 0,
+            obtained_at: Utc::now(),
+            scope: Vec::new(),
+            token_type: "bearer".to_owned(),
+        }
+    }
+
+    /// None when the lifetime of the token is unknown
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        match self.expires_in {
+            0 => None,
+            secs => Some(self.obtained_at + chrono::Duration::seconds(secs.into())),
+        }
+    }
+
+    /// Tokens of unknown lifetime are not considered expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_at()
+            .map(|expires_at| expires_at <= Utc::now())
+            .unwrap_or(false)
+    }
+
+    /// Record a lifetime of `secs` seconds from now, e.g. after validating
+    pub fn set_expires_in(&mut self, secs: u32) {
+        self.expires_in = secs;
+        self.obtained_at = Utc::now();
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Channel {
     pub broadcaster_id: BroadcasterId<'static>,
@@ -210,6 +366,343 @@ pub struct Channel {
     pub game_name: String,
     pub title: String,
     pub delay: u32,
+    #[serde(default)]
+    #[serde(deserialize_with = "null_as_empty")]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    #[serde(deserialize_with = "known_labels")]
+    pub content_classification_labels: Vec<ContentClassificationLabel>,
+    #[serde(default)]
+    pub is_branded_content: bool,
+}
+
+/// Content classification labels of a channel
+///
+/// <https://dev.twitch.tv/docs/api/reference#get-content-classification-labels>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ContentClassificationLabel {
+    DebatedSocialIssuesAndPolitics,
+    DrugsIntoxication,
+    SexualThemes,
+    ViolentGraphic,
+    Gambling,
+    ProfanityVulgarity,
+    /// Set by Twitch from the rating of the game. It cannot be changed
+    MatureGame,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Game {
+    pub id: GameId<'static>,
+    pub name: String,
+    /// Template with `{width}` and `{height}` placeholders. See `box_art`
+    pub box_art_url: String,
+    /// Empty when the game is not listed on IGDB
+    #[serde(default)]
+    pub igdb_id: String,
+}
+
+impl Game {
+    /// Url of the box art scaled to `width` x `height` pixels
+    pub fn box_art(&self, width: u32, height: u32) -> String {
+        self.box_art_url
+            .replace("{width}", &width.to_string())
+            .replace("{height}", &height.to_string())
+    }
+}
+
+/// A user following a channel. See `channels().followers`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Follower {
+    pub user_id: UserId<'static>,
+    pub user_login: String,
+    pub user_name: String,
+    pub followed_at: DateTime<Utc>,
+}
+
+/// A channel followed by a user. See `channels().followed`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FollowedChannel {
+    pub broadcaster_id: BroadcasterId<'static>,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    pub followed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum SubscriptionTier {
+    /// Tier 1, including Prime subscriptions
+    #[serde(rename = "1000")]
+    Tier1,
+    #[serde(rename = "2000")]
+    Tier2,
+    #[serde(rename = "3000")]
+    Tier3,
+}
+
+/// Page of a broadcaster's subscriptions. See `subscriptions().broadcaster`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BroadcasterSubscriptions {
+    pub data: Vec<Subscription>,
+    pub pagination: Option<Cursor>,
+    /// Number of subscriptions to the broadcaster
+    pub total: u64,
+    /// Subscriber points of the broadcaster
+    pub points: u64,
+
+    #[serde(skip)]
+    position: PagePosition,
+}
+
+helix_page!([] BroadcasterSubscriptions);
+
+/// A subscription to a broadcaster. See `subscriptions().broadcaster`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Subscription {
+    pub broadcaster_id: BroadcasterId<'static>,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    pub is_gift: bool,
+    /// None unless the subscription is a gift
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none_string")]
+    pub gifter_id: Option<UserId<'static>>,
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none_string")]
+    pub gifter_login: Option<String>,
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none_string")]
+    pub gifter_name: Option<String>,
+    pub plan_name: String,
+    pub tier: SubscriptionTier,
+    pub user_id: UserId<'static>,
+    pub user_login: String,
+    pub user_name: String,
+}
+
+/// A user's subscription, as seen by the user. See `subscriptions().check`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserSubscription {
+    pub broadcaster_id: BroadcasterId<'static>,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    pub is_gift: bool,
+    /// None unless the subscription is a gift
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none_string")]
+    pub gifter_id: Option<UserId<'static>>,
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none_string")]
+    pub gifter_login: Option<String>,
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none_string")]
+    pub gifter_name: Option<String>,
+    pub tier: SubscriptionTier,
+}
+
+/// Bits leaderboard of a broadcaster. See `bits().leaderboard`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BitsLeaderboard {
+    pub data: Vec<LeaderboardEntry>,
+    /// None for the all time leaderboard
+    #[serde(default)]
+    pub date_range: Option<DateRange>,
+    /// Number of users on the leaderboard
+    pub total: u64,
+}
+
+impl ForwardPagination for BitsLeaderboard {
+    fn cursor(&self) -> Option<&str> {
+        None
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LeaderboardEntry {
+    pub user_id: UserId<'static>,
+    pub user_login: String,
+    pub user_name: String,
+    pub rank: u32,
+    pub score: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DateRange {
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheermoteType {
+    GlobalFirstParty,
+    GlobalThirdParty,
+    ChannelCustom,
+    DisplayOnly,
+    Sponsored,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Cheermote {
+    pub prefix: String,
+    pub tiers: Vec<CheermoteTier>,
+    #[serde(rename = "type")]
+    pub cheermote_type: CheermoteType,
+    pub order: u32,
+    pub last_updated: DateTime<Utc>,
+    pub is_charitable: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheermoteTier {
+    pub min_bits: u32,
+    /// The tier level, e.g. "100" for the tier that starts at 100 bits
+    pub id: String,
+    /// Hex color of the tier, e.g. "#9c3ee8"
+    pub color: String,
+    pub images: CheermoteImages,
+    pub can_cheer: bool,
+    pub show_in_bits_card: bool,
+}
+
+impl CheermoteTier {
+    /// Url of the image in the given theme, format and scale
+    pub fn image(&self, theme: Theme, format: ImageFormat, scale: ImageScale) -> &Url {
+        let themed = match theme {
+            Theme::Dark => &self.images.dark,
+            Theme::Light => &self.images.light,
+        };
+        let scales = match format {
+            ImageFormat::Animated => &themed.animated,
+            ImageFormat::Static => &themed.static_,
+        };
+        match scale {
+            ImageScale::X1 => &scales.x1,
+            ImageScale::X1_5 => &scales.x1_5,
+            ImageScale::X2 => &scales.x2,
+            ImageScale::X3 => &scales.x3,
+            ImageScale::X4 => &scales.x4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    /// GIF
+    Animated,
+    /// PNG
+    Static,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageScale {
+    X1,
+    X1_5,
+    X2,
+    X3,
+    X4,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheermoteImages {
+    pub dark: CheermoteImageFormats,
+    pub light: CheermoteImageFormats,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheermoteImageFormats {
+    pub animated: CheermoteImageScales,
+    #[serde(rename = "static")]
+    pub static_: CheermoteImageScales,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheermoteImageScales {
+    #[serde(rename = "1")]
+    #[serde(with = "url_serde")]
+    pub x1: Url,
+    #[serde(rename = "1.5")]
+    #[serde(with = "url_serde")]
+    pub x1_5: Url,
+    #[serde(rename = "2")]
+    #[serde(with = "url_serde")]
+    pub x2: Url,
+    #[serde(rename = "3")]
+    #[serde(with = "url_serde")]
+    pub x3: Url,
+    #[serde(rename = "4")]
+    #[serde(with = "url_serde")]
+    pub x4: Url,
+}
+
+/// A purchase of an extension product with bits
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ExtensionTransaction {
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub broadcaster_id: BroadcasterId<'static>,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+    pub user_id: UserId<'static>,
+    pub user_login: String,
+    pub user_name: String,
+    /// Always "BITS_IN_EXTENSION"
+    pub product_type: String,
+    pub product_data: ProductData,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductData {
+    pub domain: String,
+    pub sku: String,
+    pub cost: ProductCost,
+    pub in_development: bool,
+    pub display_name: String,
+    /// Always empty
+    #[serde(default)]
+    pub expiration: String,
+    /// Whether the purchase was broadcast to all instances of the extension
+    pub broadcast: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProductCost {
+    pub amount: u32,
+    /// Always "bits"
+    #[serde(rename = "type")]
+    pub cost_type: String,
+}
+
+/// A channel found by `search().channels`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SearchChannel {
+    pub id: BroadcasterId<'static>,
+    pub broadcaster_login: String,
+    pub display_name: String,
+    pub broadcaster_language: String,
+    pub game_id: GameId<'static>,
+    pub game_name: String,
+    pub is_live: bool,
+    #[serde(default)]
+    #[serde(deserialize_with = "null_as_empty")]
+    pub tags: Vec<String>,
+    pub thumbnail_url: String,
+    pub title: String,
+    /// None when the channel is offline
+    #[serde(default)]
+    #[serde(deserialize_with = "empty_as_none")]
+    pub started_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -232,8 +725,73 @@ pub struct Stream {
     pub is_mature: bool,
 }
 
+/// A marker created with `streams().create_marker`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StreamMarker {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub description: String,
+    pub position_seconds: u64,
+}
+
+impl StreamMarker {
+    /// Offset of the marker from the start of the stream
+    pub fn position(&self) -> Duration {
+        Duration::from_secs(self.position_seconds)
+    }
+}
+
+/// Markers of a user, grouped by the video they were set in
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UserMarkers {
+    pub user_id: UserId<'static>,
+    pub user_name: String,
+    pub user_login: String,
+    pub videos: Vec<VideoMarkers>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct VideoMarkers {
+    pub video_id: VideoId<'static>,
+    pub markers: Vec<VideoMarker>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct VideoMarker {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub description: String,
+    pub position_seconds: u64,
+    /// Link to the position of the marker in the video
+    #[serde(rename = "URL", alias = "url", with = "url_serde")]
+    pub url: Url,
+}
+
+impl VideoMarker {
+    /// Offset of the marker from the start of the video
+    pub fn position(&self) -> Duration {
+        Duration::from_secs(self.position_seconds)
+    }
+}
+
+/// Information about an access token returned by the validate endpoint
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TokenValidation {
+    pub client_id: String,
+    /// Absent for app access tokens
+    pub login: Option<String>,
+    /// Absent for app access tokens
+    pub user_id: Option<UserId<'static>>,
+    #[serde(default)]
+    #[serde(deserialize_with = "known_scopes")]
+    pub scopes: Vec<HelixScope>,
+    pub expires_in: u32,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ApiError {
+    /* The OAuth api omits this field */
+    #[serde(default)]
     pub error: String,
     pub status: u32,
     pub message: String,
@@ -243,6 +801,133 @@ pub struct ApiError {
 mod test {
     use super::*;
 
+    #[test]
+    pub fn test_credentials_parse() {
+        let data = r#"
+        {
+          "access_token": "jostpf5q0uzmxmkba9iyug38kjtgh",
+          "expires_in": 5011271,
+          "refresh_token": "eyJfbWFyc2hhbGVyIjoiZmFsc2UiLCJfaWQiOiI0NzI3MDA3",
+          "scope": ["user:read:email", "some:future:scope"],
+          "token_type": "bearer"
+        }
+        "#;
+
+        let credentials: Credentials = serde_json::from_str(data).unwrap();
+        assert_eq!(credentials.scope, vec![HelixScope::UserReadEmail]);
+        assert!(!credentials.is_expired());
+
+        let json = serde_json::to_string(&credentials).unwrap();
+        let restored: Credentials = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, credentials);
+        assert_eq!(restored.expires_at(), credentials.expires_at());
+    }
+
+    #[test]
+    pub fn test_channel_parse() {
+        let data = r#"
+        {
+          "data": [
+            {
+              "broadcaster_id": "141981764",
+              "broadcaster_login": "twitchdev",
+              "broadcaster_name": "TwitchDev",
+              "broadcaster_language": "en",
+              "game_id": "509670",
+              "game_name": "Science & Technology",
+              "title": "TwitchDev Monthly Update // May 6, 2021",
+              "delay": 0,
+              "tags": ["DevsInTheKnow"],
+              "content_classification_labels": ["Gambling", "SomeFutureLabel", "DrugsIntoxication"],
+              "is_branded_content": true
+            },
+            {
+              "broadcaster_id": "141981765",
+              "broadcaster_login": "twitchdev2",
+              "broadcaster_name": "TwitchDev2",
+              "broadcaster_language": "en",
+              "game_id": "",
+              "game_name": "",
+              "title": "",
+              "delay": 0
+            }
+          ]
+        }
+        "#;
+
+        let actual: DataContainer<Channel> = serde_json::from_str(data).unwrap();
+        assert_eq!(actual.data[0].tags, ["DevsInTheKnow"]);
+        assert_eq!(
+            actual.data[0].content_classification_labels,
+            [
+                ContentClassificationLabel::Gambling,
+                ContentClassificationLabel::DrugsIntoxication
+            ]
+        );
+        assert!(actual.data[0].is_branded_content);
+        assert!(actual.data[1].tags.is_empty());
+        assert!(!actual.data[1].is_branded_content);
+    }
+
+    #[test]
+    pub fn test_search_channel_parse() {
+        let data = r#"
+        {
+          "data": [
+            {
+              "broadcaster_language": "en",
+              "broadcaster_login": "loserfruit",
+              "display_name": "Loserfruit",
+              "game_id": "498000",
+              "game_name": "House Flipper",
+              "id": "41245072",
+              "is_live": false,
+              "tags": [],
+              "thumbnail_url": "https://static-cdn.jtvnw.net/jtv_user_pictures/fd17325a-7dc2-46c6-8617-e90ec259501c-profile_image-300x300.png",
+              "title": "loserfruit",
+              "started_at": ""
+            },
+            {
+              "broadcaster_language": "en",
+              "broadcaster_login": "a_seagull",
+              "display_name": "A_Seagull",
+              "game_id": "506442",
+              "game_name": "DOOM Eternal",
+              "id": "19070311",
+              "is_live": true,
+              "tags": ["English"],
+              "thumbnail_url": "https://static-cdn.jtvnw.net/jtv_user_pictures/a_seagull-profile_image-4d2d235688c7dc66-300x300.png",
+              "title": "a_seagull",
+              "started_at": "2020-03-18T17:56:00Z"
+            }
+          ],
+          "pagination": {}
+        }
+        "#;
+
+        let actual: PaginationContainer<SearchChannel> = serde_json::from_str(data).unwrap();
+        assert_eq!(actual.data[0].started_at, None);
+        assert_eq!(
+            actual.data[1].started_at,
+            Some("2020-03-18T17:56:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+        assert_eq!(actual.data[1].tags, ["English"]);
+    }
+
+    #[test]
+    pub fn test_credentials_expiry() {
+        let mut credentials = Credentials::new("token", None);
+        assert_eq!(credentials.expires_at(), None);
+        assert!(!credentials.is_expired());
+
+        credentials.expires_in = 60;
+        credentials.obtained_at = Utc::now() - chrono::Duration::seconds(61);
+        assert!(credentials.is_expired());
+
+        credentials.set_expires_in(60);
+        assert!(!credentials.is_expired());
+    }
+
     #[test]
     pub fn test_clip_parse() {
         let data = r#"
@@ -264,7 +949,8 @@ mod test {
               "created_at": "2017-11-30T22:34:18Z",
               "thumbnail_url": "https://clips-media-assets.twitch.tv/157589949-preview-480x272.jpg",
               "duration": 12.9,
-              "vod_offset": 1957
+              "vod_offset": 1957,
+              "is_featured": false
             }
           ],
           "pagination": {
@@ -387,4 +1073,46 @@ mod test {
         let video = &actual.data[0];
         assert_eq!(true, video.published_at.is_none());
     }
+
+    #[test]
+    pub fn test_markers_parse() {
+        let data = r#"
+        {
+          "data": [
+            {
+              "user_id": "123",
+              "user_name": "TwitchName",
+              "user_login": "twitchname",
+              "videos": [
+                {
+                  "video_id": "456",
+                  "markers": [
+                    {
+                      "id": "106b8d6243a4f883d25ad75e6cdffdc4",
+                      "created_at": "2018-08-20T20:10:03Z",
+                      "description": "hello, this is a marker!",
+                      "position_seconds": 244,
+                      "URL": "https://twitch.tv/videos/456?t=0h4m04s"
+                    }
+                  ]
+                }
+              ]
+            }
+          ],
+          "pagination": {
+            "cursor": "eyJiIjpudWxsLCJhIjoiMjk1MjA0Mzk3OjI1Mzpib290c3RyYXBfc3RyZWFtX2tleSJ9"
+          }
+        }
+        "#;
+
+        let actual: PaginationContainer<UserMarkers> = serde_json::from_str(data).unwrap();
+        let video = &actual.data[0].videos[0];
+        assert_eq!(video.video_id, *"456");
+        let marker = &video.markers[0];
+        assert_eq!(marker.position(), Duration::from_secs(244));
+        assert_eq!(
+            marker.url.as_str(),
+            "https://twitch.tv/videos/456?t=0h4m04s"
+        );
+    }
 }
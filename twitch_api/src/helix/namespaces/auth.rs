@@ -1,6 +1,6 @@
 use crate::client::{DefaultOpts, RequestBuilder};
 
-use super::models::{ApiError, Credentials};
+use super::models::{ApiError, Credentials, TokenValidation};
 use super::*;
 
 pub struct Auth {}
@@ -13,6 +13,18 @@ impl AuthNamespace {
     ) -> RequestBuilder<Credentials, ApiError, DefaultOpts> {
         client_credentials(self.client, secret)
     }
+
+    pub fn refresh_token(
+        self,
+        secret: &str,
+        token: &str,
+    ) -> RequestBuilder<Credentials, ApiError, DefaultOpts> {
+        refresh_token(self.client, secret, token)
+    }
+
+    pub fn validate(self, token: &str) -> RequestBuilder<TokenValidation, ApiError, DefaultOpts> {
+        validate(self.client, token)
+    }
 }
 
 impl Client {
@@ -43,3 +55,24 @@ pub fn client_credentials<S: Into<String>>(
 
     return b;
 }
+
+/**
+ * https://dev.twitch.tv/docs/authentication/refresh-tokens/
+*/
+pub fn refresh_token<S: Into<String>>(
+    client: Client,
+    secret: S,
+    token: &str,
+) -> RequestBuilder<Credentials, ApiError, DefaultOpts> {
+    crate::namespace::auth::refresh_token(client.inner, secret, token)
+}
+
+/**
+ * https://dev.twitch.tv/docs/authentication/validate-tokens/
+*/
+pub fn validate(
+    client: Client,
+    token: &str,
+) -> RequestBuilder<TokenValidation, ApiError, DefaultOpts> {
+    crate::namespace::auth::validate(client.inner, token)
+}
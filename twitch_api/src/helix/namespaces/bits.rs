@@ -0,0 +1,150 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use twitch_types::{BroadcasterId, UserId};
+
+use super::models::{
+    ApiError, BitsLeaderboard, Cheermote, DataContainer, ExtensionTransaction, PaginationContainer,
+};
+use super::*;
+use crate::client::RequestBuilder;
+
+pub struct Bits {}
+type BitsNamespace = Namespace<Bits>;
+
+/// Time period covered by a bits leaderboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardPeriod {
+    Day,
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+impl LeaderboardPeriod {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            LeaderboardPeriod::Day => "day",
+            LeaderboardPeriod::Week => "week",
+            LeaderboardPeriod::Month => "month",
+            LeaderboardPeriod::Year => "year",
+            LeaderboardPeriod::All => "all",
+        }
+    }
+}
+
+impl RequestBuilder<BitsLeaderboard, ApiError, Bits> {
+    /// Number of users to return. Maximum: 100. Default: 10.
+    pub fn count(self, count: u32) -> Self {
+        self.with_query("count", count.to_string())
+    }
+
+    /// Default: `LeaderboardPeriod::All`
+    pub fn period(self, period: LeaderboardPeriod) -> Self {
+        self.with_query("period", period.to_str())
+    }
+
+    /// Start of the period. The period containing `started_at` is returned,
+    /// e.g. the week of `started_at` for a weekly leaderboard. Ignored for
+    /// `LeaderboardPeriod::All`
+    pub fn started_at(self, started_at: DateTime<Utc>) -> Self {
+        self.with_query(
+            "started_at",
+            started_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        )
+    }
+
+    /// Only return `user` and the users ranked around them
+    pub fn user<'a, Id: Into<UserId<'a>>>(self, user: Id) -> Self {
+        self.with_query("user_id", user.into())
+    }
+}
+
+impl RequestBuilder<DataContainer<Cheermote>, ApiError, Bits> {
+    /// Also return the custom cheermotes of `broadcaster`
+    pub fn broadcaster<'a, Id: Into<BroadcasterId<'a>>>(self, broadcaster: Id) -> Self {
+        self.with_query("broadcaster_id", broadcaster.into())
+    }
+}
+
+impl RequestBuilder<PaginationContainer<ExtensionTransaction>, ApiError, Bits> {
+    /// Maximum number of objects to return. Maximum: 100. Default: 20.
+    pub fn first(self, first: u32) -> Self {
+        self.with_query("first", first.to_string())
+    }
+
+    /// Only return the transactions with the given ids. Maximum: 100 ids
+    pub fn ids<Id: ToString>(mut self, ids: &[Id]) -> Self {
+        for id in ids {
+            self = self.with_query("id", id.to_string());
+        }
+        self
+    }
+}
+
+impl BitsNamespace {
+    ///Get the users that cheered the most bits in the channel of the
+    ///authenticated broadcaster
+    ///
+    ///Requires a user token of the broadcaster with the `bits:read` scope
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-bits-leaderboard>
+    pub fn leaderboard(self) -> RequestBuilder<BitsLeaderboard, ApiError, Bits> {
+        leaderboard(self.client)
+    }
+
+    ///Get the global cheermotes
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-cheermotes>
+    pub fn cheermotes(self) -> RequestBuilder<DataContainer<Cheermote>, ApiError, Bits> {
+        cheermotes(self.client)
+    }
+
+    ///Get the bits transactions of an extension, most recent first
+    ///
+    ///Requires an app access token of the extension's client
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-extension-transactions>
+    pub fn extension_transactions<S: ToString>(
+        self,
+        extension_id: S,
+    ) -> RequestBuilder<PaginationContainer<ExtensionTransaction>, ApiError, Bits> {
+        extension_transactions(self.client, extension_id)
+    }
+}
+
+impl Client {
+    pub fn bits(&self) -> BitsNamespace {
+        BitsNamespace::new(self)
+    }
+}
+
+///Get the bits leaderboard of the authenticated broadcaster
+///
+///<https://dev.twitch.tv/docs/api/reference#get-bits-leaderboard>
+pub fn leaderboard(client: Client) -> RequestBuilder<BitsLeaderboard, ApiError, Bits> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/bits/leaderboard";
+    RequestBuilder::new(client, url, Method::GET)
+}
+
+///Get the global cheermotes
+///
+///<https://dev.twitch.tv/docs/api/reference#get-cheermotes>
+pub fn cheermotes(client: Client) -> RequestBuilder<DataContainer<Cheermote>, ApiError, Bits> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/bits/cheermotes";
+    RequestBuilder::new(client, url, Method::GET)
+}
+
+///Get the bits transactions of an extension
+///
+///<https://dev.twitch.tv/docs/api/reference#get-extension-transactions>
+pub fn extension_transactions<S: ToString>(
+    client: Client,
+    extension_id: S,
+) -> RequestBuilder<PaginationContainer<ExtensionTransaction>, ApiError, Bits> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/extensions/transactions";
+    let b = RequestBuilder::new(client, url, Method::GET);
+    b.with_query("extension_id", extension_id.to_string())
+}
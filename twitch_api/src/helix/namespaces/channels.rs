@@ -1,13 +1,44 @@
-use twitch_types::BroadcasterId;
+use std::future::IntoFuture;
+use std::pin::Pin;
 
-use crate::client::RequestBuilder;
+use futures::Future;
+use twitch_types::{BroadcasterId, GameId, UserId};
 
-use super::models::{Channel, DataContainer};
+use crate::client::{invalidate_cache, require_scope, HelixScope, RequestBuilder};
+use crate::error::{Error, ValidationError};
+
+use super::models::{
+    ApiError, Channel, ContentClassificationLabel, DataContainer, FollowedChannel, Follower,
+    PaginationContainer,
+};
 use super::*;
 
 pub struct Channels {}
 type ChannelNamespace = Namespace<Channels>;
 
+pub struct Follows {}
+
+impl<T> RequestBuilder<T, ApiError, Follows> {
+    /// Maximum number of objects to return. Maximum: 100. Default: 20.
+    pub fn first(self, first: u32) -> Self {
+        self.with_query("first", first.to_string())
+    }
+}
+
+impl RequestBuilder<PaginationContainer<Follower>, ApiError, Follows> {
+    /// Only return the follow of `user`, if any
+    pub fn user<'a, Id: Into<UserId<'a>>>(self, user: Id) -> Self {
+        self.with_query("user_id", user.into())
+    }
+}
+
+impl RequestBuilder<PaginationContainer<FollowedChannel>, ApiError, Follows> {
+    /// Only return the follow of `broadcaster`, if any
+    pub fn broadcaster<'a, Id: Into<BroadcasterId<'a>>>(self, broadcaster: Id) -> Self {
+        self.with_query("broadcaster_id", broadcaster.into())
+    }
+}
+
 impl ChannelNamespace {
     pub fn channel<'a, Id: Into<BroadcasterId<'a>>>(
         self,
@@ -15,6 +46,50 @@ impl ChannelNamespace {
     ) -> RequestBuilder<DataContainer<Channel>> {
         channels(self.client, id)
     }
+
+    ///Get the channels of several broadcasters
+    ///
+    ///Lists longer than 100 ids are split into several requests
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-channel-information>
+    pub fn by_ids<Id: ToString>(self, ids: &[Id]) -> RequestBuilder<DataContainer<Channel>> {
+        by_ids(self.client, ids)
+    }
+
+    ///Get the users following a channel, most recent first
+    ///
+    ///`total` is always reported. The followers themselves are only listed
+    ///for a user token of the broadcaster or one of its moderators with the
+    ///`moderator:read:followers` scope
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-channel-followers>
+    pub fn followers<'a, Id: Into<BroadcasterId<'a>>>(
+        self,
+        id: Id,
+    ) -> RequestBuilder<PaginationContainer<Follower>, ApiError, Follows> {
+        followers(self.client, id)
+    }
+
+    ///Get the channels a user follows, most recent first
+    ///
+    ///Requires a user token of `user` with the `user:read:follows` scope
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-followed-channels>
+    pub fn followed<'a, Id: Into<UserId<'a>>>(
+        self,
+        user: Id,
+    ) -> RequestBuilder<PaginationContainer<FollowedChannel>, ApiError, Follows> {
+        followed(self.client, user)
+    }
+
+    ///Update the title, game and other properties of a channel
+    ///
+    ///Requires a user token with the `channel:manage:broadcast` scope
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#modify-channel-information>
+    pub fn update<'a, Id: Into<BroadcasterId<'a>>>(self, id: Id) -> ChannelUpdate {
+        update(self.client, id)
+    }
 }
 
 impl Client {
@@ -34,3 +109,229 @@ pub fn channels<'a, Id: Into<BroadcasterId<'a>>>(
 
     return b;
 }
+
+///Get the channels of several broadcasters
+///
+///<https://dev.twitch.tv/docs/api/reference#get-channel-information>
+pub fn by_ids<Id: ToString>(client: Client, ids: &[Id]) -> RequestBuilder<DataContainer<Channel>> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/channels";
+    let b = RequestBuilder::new(client, url, Method::GET);
+    b.with_batched_query("broadcaster_id", ids.iter().map(|id| id.to_string()))
+}
+
+///Get the users following a channel
+///
+///<https://dev.twitch.tv/docs/api/reference#get-channel-followers>
+pub fn followers<'a, Id: Into<BroadcasterId<'a>>>(
+    client: Client,
+    id: Id,
+) -> RequestBuilder<PaginationContainer<Follower>, ApiError, Follows> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/channels/followers";
+    let b = RequestBuilder::new(client, url, Method::GET);
+    b.with_query("broadcaster_id", id.into())
+}
+
+///Get the channels a user follows
+///
+///<https://dev.twitch.tv/docs/api/reference#get-followed-channels>
+pub fn followed<'a, Id: Into<UserId<'a>>>(
+    client: Client,
+    user: Id,
+) -> RequestBuilder<PaginationContainer<FollowedChannel>, ApiError, Follows> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/channels/followed";
+    let b = RequestBuilder::new(client, url, Method::GET);
+    b.with_query("user_id", user.into())
+}
+
+///Update the title, game and other properties of a channel
+///
+///<https://dev.twitch.tv/docs/api/reference#modify-channel-information>
+pub fn update<'a, Id: Into<BroadcasterId<'a>>>(client: Client, id: Id) -> ChannelUpdate {
+    ChannelUpdate {
+        client,
+        broadcaster_id: id.into().into(),
+        body: ChannelUpdateBody::default(),
+    }
+}
+
+const MAX_TITLE_LENGTH: usize = 140;
+const MAX_DELAY: u32 = 900;
+const MAX_TAGS: usize = 10;
+const MAX_TAG_LENGTH: usize = 25;
+
+/// Changes to a channel. Only the properties that are set are changed.
+///
+/// The changes are validated before they are sent. Awaiting the update fails
+/// with a validation error when they would be rejected by Twitch
+pub struct ChannelUpdate {
+    client: Client,
+    broadcaster_id: String,
+    body: ChannelUpdateBody,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ChannelUpdateBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    game_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    broadcaster_language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delay: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    content_classification_labels: Vec<LabelUpdate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_branded_content: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct LabelUpdate {
+    id: ContentClassificationLabel,
+    is_enabled: bool,
+}
+
+impl ChannelUpdate {
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.body.title = Some(title.into());
+        self
+    }
+
+    /// The game or category being played. "0" removes the game
+    pub fn game<'a, Id: Into<GameId<'a>>>(mut self, id: Id) -> Self {
+        self.body.game_id = Some(id.into().into());
+        self
+    }
+
+    /// ISO 639-1 code of the language of the stream, or "other"
+    pub fn language<S: Into<String>>(mut self, language: S) -> Self {
+        self.body.broadcaster_language = Some(language.into());
+        self
+    }
+
+    /// Delay of the stream in seconds. Only available to partners
+    pub fn delay(mut self, delay: u32) -> Self {
+        self.body.delay = Some(delay);
+        self
+    }
+
+    /// Replace all tags of the channel. An empty list removes all tags
+    pub fn tags<S: ToString>(mut self, tags: &[S]) -> Self {
+        self.body.tags = Some(tags.iter().map(|tag| tag.to_string()).collect());
+        self
+    }
+
+    /// Enable or disable a content classification label. Labels that are not
+    /// mentioned keep their state
+    pub fn content_classification_label(
+        mut self,
+        label: ContentClassificationLabel,
+        enabled: bool,
+    ) -> Self {
+        let labels = &mut self.body.content_classification_labels;
+        labels.retain(|update| update.id != label);
+        labels.push(LabelUpdate {
+            id: label,
+            is_enabled: enabled,
+        });
+        self
+    }
+
+    /// Whether the stream contains branded content
+    pub fn branded_content(mut self, branded: bool) -> Self {
+        self.body.is_branded_content = Some(branded);
+        self
+    }
+
+    /// Check the changes against the limits of Helix without sending them
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let body = &self.body;
+        require_scope(&self.client.inner, HelixScope::ChannelManageBroadcast)?;
+
+        if body.game_id.is_none()
+            && body.broadcaster_language.is_none()
+            && body.title.is_none()
+            && body.delay.is_none()
+            && body.tags.is_none()
+            && body.content_classification_labels.is_empty()
+            && body.is_branded_content.is_none()
+        {
+            return Err(ValidationError::NoChanges);
+        }
+
+        if let Some(title) = &body.title {
+            let len = title.chars().count();
+            if title.trim().is_empty() {
+                return Err(ValidationError::EmptyTitle);
+            } else if len > MAX_TITLE_LENGTH {
+                return Err(ValidationError::TitleTooLong(len));
+            }
+        }
+
+        if let Some(language) = &body.broadcaster_language {
+            let iso = language.len() == 2 && language.chars().all(|c| c.is_ascii_lowercase());
+            if !iso && language != "other" {
+                return Err(ValidationError::InvalidLanguage(language.clone()));
+            }
+        }
+
+        match body.delay {
+            Some(delay) if delay > MAX_DELAY => return Err(ValidationError::DelayTooLong(delay)),
+            _ => {}
+        }
+
+        if let Some(tags) = &body.tags {
+            if tags.len() > MAX_TAGS {
+                return Err(ValidationError::TooManyTags(tags.len()));
+            }
+            let invalid = |tag: &&String| {
+                tag.is_empty()
+                    || tag.chars().count() > MAX_TAG_LENGTH
+                    || !tag.chars().all(char::is_alphanumeric)
+            };
+            if let Some(tag) = tags.iter().find(invalid) {
+                return Err(ValidationError::InvalidTag(tag.clone()));
+            }
+        }
+
+        let labels = &body.content_classification_labels;
+        if let Some(update) = labels
+            .iter()
+            .find(|update| update.id == ContentClassificationLabel::MatureGame)
+        {
+            return Err(ValidationError::ReadOnlyLabel(update.id));
+        }
+
+        Ok(())
+    }
+}
+
+impl IntoFuture for ChannelUpdate {
+    type Output = Result<(), Error>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        if let Err(err) = self.validate() {
+            return Box::pin(async move { Err(err.into()) });
+        }
+
+        let body = serde_json::to_value(&self.body).unwrap();
+        let client = self.client.inner;
+        let url = client.api_base_uri().to_owned() + "/channels";
+        let b: RequestBuilder<()> = RequestBuilder::new(client.clone(), url, Method::PATCH)
+            .with_query("broadcaster_id", self.broadcaster_id)
+            .with_body(body);
+
+        Box::pin(async move {
+            b.await?;
+            /* Cached lookups of the channel would show it as it was before */
+            invalidate_cache(&client, "/channels").await;
+            Ok(())
+        })
+    }
+}
@@ -1,30 +1,48 @@
-use super::models::{ApiError, Clip, DataContainer, PaginationContainer};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use super::models::{ApiError, Clip, CreatedClip, DataContainer, PaginationContainer};
 use super::*;
-use crate::client::{DefaultOpts, RequestBuilder};
-use twitch_types::{BroadcasterId, GameId, UserId};
+use crate::client::{DefaultOpts, RatelimitKey, RequestBuilder};
+use crate::error::Error;
+use twitch_types::{BroadcasterId, ClipId, GameId};
+
+/// How long Twitch may take to create a clip. Clips that do not show up
+/// within this time have failed
+const CLIP_DEADLINE: Duration = Duration::from_secs(15);
+const FIRST_POLL_DELAY: Duration = Duration::from_millis(250);
 
 pub struct Clips {}
 type ClipsNamespace = Namespace<Clips>;
 
 impl<T> RequestBuilder<T, ApiError, Clips> {
-    ///Ending date/time for returned clips, in RFC3339 format. (Note that the
-    ///seconds value is ignored.) If this is specified, started_at also must be
-    ///specified; otherwise, the time period is ignored.
-    pub fn ended_at<S: Into<String>>(self, end: S) -> Self {
-        self.with_query("ended_at", end)
+    ///Ending date/time for returned clips. (Note that the seconds value is
+    ///ignored.) If this is specified, started_at also must be specified;
+    ///otherwise, the time period is ignored.
+    pub fn ended_at(self, end: DateTime<Utc>) -> Self {
+        self.with_query("ended_at", end.to_rfc3339_opts(SecondsFormat::Secs, true))
     }
 
-    ///Starting date/time for returned clips, in RFC3339 format. (The seconds
-    ///value is ignored.) If this is specified, ended_at also should be
-    ///specified; otherwise, the ended_at date/time will be 1 week after the
-    ///started_at value.
-    pub fn started_at<S: Into<String>>(self, start: S) -> Self {
-        self.with_query("started_at", start)
+    ///Starting date/time for returned clips. (The seconds value is ignored.)
+    ///If this is specified, ended_at also should be specified; otherwise, the
+    ///ended_at date/time will be 1 week after the started_at value.
+    pub fn started_at(self, start: DateTime<Utc>) -> Self {
+        self.with_query(
+            "started_at",
+            start.to_rfc3339_opts(SecondsFormat::Secs, true),
+        )
     }
 
+    ///Maximum number of objects to return. Maximum: 100. Default: 20.
     pub fn first(self, first: u32) -> Self {
         self.with_query("first", first.to_string())
     }
+
+    ///Only return clips that are, or are not, featured by the broadcaster
+    pub fn is_featured(self, featured: bool) -> Self {
+        self.with_query("is_featured", featured.to_string())
+    }
 }
 
 impl ClipsNamespace {
@@ -36,7 +54,7 @@ impl ClipsNamespace {
     pub fn by_game<'a, Id: Into<GameId<'a>>>(
         self,
         id: Id,
-    ) -> RequestBuilder<DataContainer<Clip>, ApiError, Clips> {
+    ) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
         by_game(self.client, id)
     }
 
@@ -54,13 +72,42 @@ impl ClipsNamespace {
 
     ///Get a list of clips given by id
     ///
+    ///Lists longer than 100 ids are split into several requests
+    ///
     ///<https://dev.twitch.tv/docs/api/reference#get-clips>
-    pub fn by_clips<'a, Id: ToString>(
+    pub fn by_clips<Id: ToString>(
         self,
         ids: &[Id],
-    ) -> RequestBuilder<DataContainer<Clip>, ApiError, DefaultOpts> {
+    ) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
         by_clips(self.client, ids)
     }
+
+    ///Create a clip of the broadcaster's live stream
+    ///
+    ///Clips are created asynchronously. The response only holds the id of the
+    ///clip and the url to edit it, see `wait_for_clip`. Requires a user token
+    ///with the `clips:edit` scope. Clip creation has a ratelimit of its own
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#create-clip>
+    pub fn create<'a, Id: Into<BroadcasterId<'a>>>(
+        self,
+        broadcaster: Id,
+        has_delay: bool,
+    ) -> RequestBuilder<DataContainer<CreatedClip>, ApiError, DefaultOpts> {
+        create(self.client, broadcaster, has_delay)
+    }
+
+    ///Wait until the clip `id` has been created
+    ///
+    ///Polls for the clip with increasing delays, bypassing the response cache.
+    ///Returns None when the clip does not exist after 15 seconds, in which
+    ///case creating it failed
+    pub async fn wait_for_clip<'a, Id: Into<ClipId<'a>>>(
+        self,
+        id: Id,
+    ) -> Result<Option<Clip>, Error> {
+        wait_for_clip(self.client, id).await
+    }
 }
 
 impl Client {
@@ -74,11 +121,9 @@ impl Client {
 
 fn init_clips_request_builder(
     client: Client,
-) -> RequestBuilder<DataContainer<Clip>, ApiError, Clips> {
+) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
     let url = client.inner.api_base_uri().to_string() + "/clips";
-    let b = RequestBuilder::new(client.inner, url, Method::GET);
-
-    return b;
+    RequestBuilder::new(client.inner, url, Method::GET)
 }
 
 ///Get clips for a game with an optional time range
@@ -89,10 +134,8 @@ fn init_clips_request_builder(
 pub fn by_game<'a, Id: Into<GameId<'a>>>(
     client: Client,
     id: Id,
-) -> RequestBuilder<DataContainer<Clip>, ApiError, Clips> {
-    let mut b = init_clips_request_builder(client);
-    b = b.with_query("game_id", id.into());
-    b
+) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
+    init_clips_request_builder(client).with_query("game_id", id.into())
 }
 
 ///Get clips for a broadcaster with an optional time range
@@ -100,27 +143,58 @@ pub fn by_game<'a, Id: Into<GameId<'a>>>(
 ///Results are ordered by view count
 ///
 ///<https://dev.twitch.tv/docs/api/reference#get-clips>
-pub fn by_broadcaster<'a, Id: Into<UserId<'a>>>(
+pub fn by_broadcaster<'a, Id: Into<BroadcasterId<'a>>>(
     client: Client,
     id: Id,
 ) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
-    let url = client.inner.api_base_uri().to_string() + "/clips";
-    let mut b = RequestBuilder::new(client.inner, url, Method::GET);
-    b = b.with_query("broadcaster_id", id.into());
-    b
+    init_clips_request_builder(client).with_query("broadcaster_id", id.into())
 }
 
 ///Get a list of clips by their id
 ///
 ///<https://dev.twitch.tv/docs/api/reference#get-clips>
-pub fn by_clips<'a, Id: ToString>(
+pub fn by_clips<Id: ToString>(
     client: Client,
     ids: &[Id],
-) -> RequestBuilder<DataContainer<Clip>, ApiError, DefaultOpts> {
+) -> RequestBuilder<PaginationContainer<Clip>, ApiError, Clips> {
+    init_clips_request_builder(client).with_batched_query("id", ids.iter().map(|id| id.to_string()))
+}
+
+///Create a clip of the broadcaster's live stream
+///
+///<https://dev.twitch.tv/docs/api/reference#create-clip>
+pub fn create<'a, Id: Into<BroadcasterId<'a>>>(
+    client: Client,
+    broadcaster: Id,
+    has_delay: bool,
+) -> RequestBuilder<DataContainer<CreatedClip>, ApiError, DefaultOpts> {
     let url = client.inner.api_base_uri().to_string() + "/clips";
-    let mut b = RequestBuilder::new(client.inner, url, Method::GET);
-    for id in ids {
-        b = b.with_query("id", id.to_string());
+    let b = RequestBuilder::new(client.inner, url, Method::POST);
+    b.with_query("broadcaster_id", broadcaster.into())
+        .with_query("has_delay", has_delay.to_string())
+        .with_endpoint_ratelimit(RatelimitKey::ClipCreation)
+}
+
+///Wait until the clip `id` has been created
+pub async fn wait_for_clip<'a, Id: Into<ClipId<'a>>>(
+    client: Client,
+    id: Id,
+) -> Result<Option<Clip>, Error> {
+    let id = id.into().to_string();
+    let deadline = Instant::now() + CLIP_DEADLINE;
+    let mut delay = FIRST_POLL_DELAY;
+    loop {
+        /* A cached response would hide the clip once it exists */
+        let clips = by_clips(client.clone(), &[&id]).cache(false).await?;
+        if let Some(clip) = clips.data.into_iter().next() {
+            return Ok(Some(clip));
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        tokio::time::sleep(delay.min(deadline - now)).await;
+        delay *= 2;
     }
-    b
 }
@@ -0,0 +1,95 @@
+use super::models::{ApiError, DataContainer, Game, PaginationContainer};
+use super::*;
+use crate::client::{encode, RequestBuilder};
+
+pub struct Games {}
+type GamesNamespace = Namespace<Games>;
+
+impl<T> RequestBuilder<T, ApiError, Games> {
+    /// Maximum number of objects to return. Maximum: 100. Default: 20.
+    pub fn first(self, first: u32) -> Self {
+        self.with_query("first", first.to_string())
+    }
+}
+
+impl GamesNamespace {
+    ///Get games by their id
+    ///
+    ///Lists longer than 100 ids are split into several requests
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-games>
+    pub fn by_ids<Id: ToString>(self, ids: &[Id]) -> RequestBuilder<DataContainer<Game>> {
+        by_ids(self.client, ids)
+    }
+
+    ///Get games by their exact name
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-games>
+    pub fn by_names<S: ToString>(self, names: &[S]) -> RequestBuilder<DataContainer<Game>> {
+        by_names(self.client, names)
+    }
+
+    ///Get games by their IGDB id
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-games>
+    pub fn by_igdb_ids<Id: ToString>(self, ids: &[Id]) -> RequestBuilder<DataContainer<Game>> {
+        by_igdb_ids(self.client, ids)
+    }
+
+    ///Get games sorted by number of current viewers, most popular first
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-top-games>
+    pub fn top(self) -> RequestBuilder<PaginationContainer<Game>, ApiError, Games> {
+        top(self.client)
+    }
+}
+
+impl Client {
+    pub fn games(&self) -> GamesNamespace {
+        GamesNamespace::new(self)
+    }
+}
+
+fn get_games<S: ToString>(
+    client: Client,
+    key: &str,
+    values: &[S],
+) -> RequestBuilder<DataContainer<Game>> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/games";
+    let b = RequestBuilder::new(client, url, Method::GET);
+    b.with_batched_query(key, values.iter().map(|value| encode(&value.to_string())))
+}
+
+///Get games by their id
+///
+///<https://dev.twitch.tv/docs/api/reference#get-games>
+pub fn by_ids<Id: ToString>(client: Client, ids: &[Id]) -> RequestBuilder<DataContainer<Game>> {
+    get_games(client, "id", ids)
+}
+
+///Get games by their exact name
+///
+///<https://dev.twitch.tv/docs/api/reference#get-games>
+pub fn by_names<S: ToString>(client: Client, names: &[S]) -> RequestBuilder<DataContainer<Game>> {
+    get_games(client, "name", names)
+}
+
+///Get games by their IGDB id
+///
+///<https://dev.twitch.tv/docs/api/reference#get-games>
+pub fn by_igdb_ids<Id: ToString>(
+    client: Client,
+    ids: &[Id],
+) -> RequestBuilder<DataContainer<Game>> {
+    get_games(client, "igdb_id", ids)
+}
+
+///Get games sorted by number of current viewers
+///
+///<https://dev.twitch.tv/docs/api/reference#get-top-games>
+pub fn top(client: Client) -> RequestBuilder<PaginationContainer<Game>, ApiError, Games> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/games/top";
+    RequestBuilder::new(client, url, Method::GET)
+}
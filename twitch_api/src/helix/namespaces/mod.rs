@@ -7,9 +7,13 @@ pub use hyper::Method;
 pub use std::collections::BTreeMap;
 
 pub mod auth;
+pub mod bits;
 pub mod channels;
 pub mod clips;
+pub mod games;
+pub mod search;
 pub mod streams;
+pub mod subscriptions;
 pub mod users;
 pub mod videos;
 
@@ -0,0 +1,74 @@
+use super::models::{ApiError, Game, PaginationContainer, SearchChannel};
+use super::*;
+use crate::client::{encode, RequestBuilder};
+
+pub struct Search {}
+type SearchNamespace = Namespace<Search>;
+
+impl<T> RequestBuilder<T, ApiError, Search> {
+    /// Maximum number of objects to return. Maximum: 100. Default: 20.
+    pub fn first(self, first: u32) -> Self {
+        self.with_query("first", first.to_string())
+    }
+}
+
+impl RequestBuilder<PaginationContainer<SearchChannel>, ApiError, Search> {
+    /// Only return channels that are streaming
+    pub fn live_only(self, live_only: bool) -> Self {
+        self.with_query("live_only", live_only.to_string())
+    }
+}
+
+impl SearchNamespace {
+    ///Find games and categories whose name matches `query`
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#search-categories>
+    pub fn categories(
+        self,
+        query: &str,
+    ) -> RequestBuilder<PaginationContainer<Game>, ApiError, Search> {
+        categories(self.client, query)
+    }
+
+    ///Find channels whose name or title matches `query`
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#search-channels>
+    pub fn channels(
+        self,
+        query: &str,
+    ) -> RequestBuilder<PaginationContainer<SearchChannel>, ApiError, Search> {
+        channels(self.client, query)
+    }
+}
+
+impl Client {
+    pub fn search(&self) -> SearchNamespace {
+        SearchNamespace::new(self)
+    }
+}
+
+///Find games and categories whose name matches `query`
+///
+///<https://dev.twitch.tv/docs/api/reference#search-categories>
+pub fn categories(
+    client: Client,
+    query: &str,
+) -> RequestBuilder<PaginationContainer<Game>, ApiError, Search> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/search/categories";
+    let b = RequestBuilder::new(client, url, Method::GET);
+    b.with_query("query", encode(query))
+}
+
+///Find channels whose name or title matches `query`
+///
+///<https://dev.twitch.tv/docs/api/reference#search-channels>
+pub fn channels(
+    client: Client,
+    query: &str,
+) -> RequestBuilder<PaginationContainer<SearchChannel>, ApiError, Search> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/search/channels";
+    let b = RequestBuilder::new(client, url, Method::GET);
+    b.with_query("query", encode(query))
+}
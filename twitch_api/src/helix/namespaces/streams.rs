@@ -1,12 +1,26 @@
-use crate::client::RequestBuilder;
+use std::future::IntoFuture;
+use std::pin::Pin;
 
-use super::models::{ApiError, PaginationContainer, Stream};
+use futures::Future;
+use twitch_types::{UserId, VideoId};
+
+use crate::client::{require_scope, HelixScope, RequestBuilder};
+use crate::error::{Error, ValidationError};
+
+use super::models::{
+    ApiError, DataContainer, PaginationContainer, Stream, StreamMarker, UserMarkers,
+};
 use super::*;
 
 pub struct Streams {}
 type StreamNamespace = Namespace<Streams>;
 type StreamBuilder = RequestBuilder<PaginationContainer<Stream>, ApiError, Streams>;
 
+pub struct Markers {}
+type MarkersBuilder = RequestBuilder<PaginationContainer<UserMarkers>, ApiError, Markers>;
+
+const MAX_DESCRIPTION_LENGTH: usize = 140;
+
 impl Client {
     pub fn streams(&self) -> StreamNamespace {
         StreamNamespace::new(self)
@@ -20,10 +34,40 @@ impl<T, E> RequestBuilder<T, E, Streams> {
     }
 }
 
+impl<T, E> RequestBuilder<T, E, Markers> {
+    /// Maximum number of markers to return. Maximum: 100. Default: 20.
+    pub fn first(self, first: u32) -> Self {
+        self.with_query("first", first.to_string())
+    }
+}
+
 impl StreamNamespace {
     pub fn get(self) -> StreamBuilder {
         streams(self.client)
     }
+
+    ///Mark the current position of the user's live stream
+    ///
+    ///Requires a user token with the `channel:manage:broadcast` scope
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#create-stream-marker>
+    pub fn create_marker<'a, Id: Into<UserId<'a>>>(self, user: Id) -> MarkerCreate {
+        create_marker(self.client, user)
+    }
+
+    ///Get the markers of the user's most recent videos, grouped by video
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-stream-markers>
+    pub fn markers_by_user<'a, Id: Into<UserId<'a>>>(self, user: Id) -> MarkersBuilder {
+        markers_by_user(self.client, user)
+    }
+
+    ///Get the markers of a video
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-stream-markers>
+    pub fn markers_by_video<'a, Id: Into<VideoId<'a>>>(self, video: Id) -> MarkersBuilder {
+        markers_by_video(self.client, video)
+    }
 }
 
 pub fn streams(client: Client) -> StreamBuilder {
@@ -33,3 +77,103 @@ pub fn streams(client: Client) -> StreamBuilder {
 
     return b;
 }
+
+///Mark the current position of the user's live stream
+///
+///<https://dev.twitch.tv/docs/api/reference#create-stream-marker>
+pub fn create_marker<'a, Id: Into<UserId<'a>>>(client: Client, user: Id) -> MarkerCreate {
+    MarkerCreate {
+        client,
+        body: MarkerCreateBody {
+            user_id: user.into().into(),
+            description: None,
+        },
+    }
+}
+
+fn markers(client: Client, key: &str, id: String) -> MarkersBuilder {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/streams/markers";
+    RequestBuilder::new(client, url, Method::GET).with_query(key, id)
+}
+
+///Get the markers of the user's most recent videos
+///
+///<https://dev.twitch.tv/docs/api/reference#get-stream-markers>
+pub fn markers_by_user<'a, Id: Into<UserId<'a>>>(client: Client, user: Id) -> MarkersBuilder {
+    markers(client, "user_id", user.into().into())
+}
+
+///Get the markers of a video
+///
+///<https://dev.twitch.tv/docs/api/reference#get-stream-markers>
+pub fn markers_by_video<'a, Id: Into<VideoId<'a>>>(client: Client, video: Id) -> MarkersBuilder {
+    markers(client, "video_id", video.into().into())
+}
+
+/// A marker to create in a live stream.
+///
+/// Awaiting it checks that the stream is live before the marker is created,
+/// and fails with a validation error otherwise
+pub struct MarkerCreate {
+    client: Client,
+    body: MarkerCreateBody,
+}
+
+#[derive(Debug, Serialize)]
+struct MarkerCreateBody {
+    user_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+impl MarkerCreate {
+    /// Short description of the marker, at most 140 characters
+    pub fn description<S: Into<String>>(mut self, description: S) -> Self {
+        self.body.description = Some(description.into());
+        self
+    }
+
+    /// Check the marker against the limits of Helix without sending it. Whether
+    /// the stream is live is only checked when the marker is created
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        require_scope(&self.client.inner, HelixScope::ChannelManageBroadcast)?;
+
+        if let Some(description) = &self.body.description {
+            let len = description.chars().count();
+            if len > MAX_DESCRIPTION_LENGTH {
+                return Err(ValidationError::DescriptionTooLong(len));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl IntoFuture for MarkerCreate {
+    type Output = Result<DataContainer<StreamMarker>, Error>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        if let Err(err) = self.validate() {
+            return Box::pin(async move { Err(err.into()) });
+        }
+
+        /* A cached response could be from before the stream went live or ended */
+        let live = streams(self.client.clone())
+            .with_query("user_id", self.body.user_id.clone())
+            .cache(false);
+        let body = serde_json::to_value(&self.body).unwrap();
+        let client = self.client.inner;
+        let url = client.api_base_uri().to_owned() + "/streams/markers";
+        let create: RequestBuilder<DataContainer<StreamMarker>> =
+            RequestBuilder::new(client, url, Method::POST).with_body(body);
+
+        Box::pin(async move {
+            if live.await?.data.is_empty() {
+                return Err(ValidationError::NotLive.into());
+            }
+            create.await
+        })
+    }
+}
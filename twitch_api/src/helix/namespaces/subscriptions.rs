@@ -0,0 +1,126 @@
+use std::future::IntoFuture;
+use std::pin::Pin;
+
+use futures::Future;
+use twitch_types::{BroadcasterId, UserId};
+
+use super::models::{ApiError, BroadcasterSubscriptions, DataContainer, UserSubscription};
+use super::*;
+use crate::client::RequestBuilder;
+use crate::error::Error;
+
+pub struct Subscriptions {}
+type SubscriptionsNamespace = Namespace<Subscriptions>;
+
+impl<T> RequestBuilder<T, ApiError, Subscriptions> {
+    /// Maximum number of objects to return. Maximum: 100. Default: 20.
+    pub fn first(self, first: u32) -> Self {
+        self.with_query("first", first.to_string())
+    }
+
+    /// Only return the subscriptions of `users`. Maximum: 100 users
+    pub fn users<Id: ToString>(mut self, users: &[Id]) -> Self {
+        for user in users {
+            self = self.with_query("user_id", user.to_string());
+        }
+        self
+    }
+}
+
+impl SubscriptionsNamespace {
+    ///Get the subscribers of a broadcaster
+    ///
+    ///`total` and `points` of the returned pages hold the number of
+    ///subscriptions and the subscriber points of the broadcaster. Requires a
+    ///user token of the broadcaster with the `channel:read:subscriptions`
+    ///scope
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#get-broadcaster-subscriptions>
+    pub fn broadcaster<'a, Id: Into<BroadcasterId<'a>>>(
+        self,
+        id: Id,
+    ) -> RequestBuilder<BroadcasterSubscriptions, ApiError, Subscriptions> {
+        broadcaster(self.client, id)
+    }
+
+    ///Check whether `user` subscribes to `broadcaster`
+    ///
+    ///Requires a user token of `user` with the `user:read:subscriptions`
+    ///scope
+    ///
+    ///<https://dev.twitch.tv/docs/api/reference#check-user-subscription>
+    pub fn check<'a, 'b, B: Into<BroadcasterId<'a>>, U: Into<UserId<'b>>>(
+        self,
+        broadcaster: B,
+        user: U,
+    ) -> SubscriptionCheck {
+        check(self.client, broadcaster, user)
+    }
+}
+
+impl Client {
+    pub fn subscriptions(&self) -> SubscriptionsNamespace {
+        SubscriptionsNamespace::new(self)
+    }
+}
+
+///Get the subscribers of a broadcaster
+///
+///<https://dev.twitch.tv/docs/api/reference#get-broadcaster-subscriptions>
+pub fn broadcaster<'a, Id: Into<BroadcasterId<'a>>>(
+    client: Client,
+    id: Id,
+) -> RequestBuilder<BroadcasterSubscriptions, ApiError, Subscriptions> {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/subscriptions";
+    let b = RequestBuilder::new(client, url, Method::GET);
+    b.with_query("broadcaster_id", id.into())
+}
+
+///Check whether `user` subscribes to `broadcaster`
+///
+///<https://dev.twitch.tv/docs/api/reference#check-user-subscription>
+pub fn check<'a, 'b, B: Into<BroadcasterId<'a>>, U: Into<UserId<'b>>>(
+    client: Client,
+    broadcaster: B,
+    user: U,
+) -> SubscriptionCheck {
+    let client = client.inner;
+    let url = client.api_base_uri().to_owned() + "/subscriptions/user";
+    let b = RequestBuilder::new(client, url, Method::GET)
+        .with_query("broadcaster_id", broadcaster.into())
+        .with_query("user_id", user.into());
+    SubscriptionCheck { inner: b }
+}
+
+/// Request for a user's subscription. Resolves to None when the user does
+/// not subscribe to the broadcaster
+pub struct SubscriptionCheck {
+    inner: RequestBuilder<DataContainer<UserSubscription>>,
+}
+
+impl SubscriptionCheck {
+    /// Send the request with the user access `token`. See `Client::as_user`
+    pub fn as_user(self, token: &str) -> Self {
+        SubscriptionCheck {
+            inner: self.inner.as_user(token),
+        }
+    }
+}
+
+impl IntoFuture for SubscriptionCheck {
+    type Output = Result<Option<UserSubscription>, Error>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let request = self.inner.into_future();
+        Box::pin(async move {
+            match request.await {
+                Ok(subscriptions) => Ok(subscriptions.data.into_iter().next()),
+                /* Helix answers with 404 when there is no subscription */
+                Err(e) if e.get_api_error().map(|e| e.status) == Some(404) => Ok(None),
+                Err(e) => Err(e),
+            }
+        })
+    }
+}
@@ -8,6 +8,8 @@ pub struct Users {}
 type UsersNamespace = Namespace<Users>;
 
 impl UsersNamespace {
+    /// Lookup users by id or login. Lists longer than 100 entries are split
+    /// into several requests
     pub fn users<S1: ToString, S2: ToString>(
         self,
         ids: &[S1],
@@ -33,17 +35,11 @@ pub fn users<S1: ToString, S2: ToString>(
 ) -> RequestBuilder<DataContainer<User>> {
     let client = client.inner;
     let url = client.api_base_uri().to_string() + "/users";
-    let mut b = RequestBuilder::new(client, url, Method::GET);
+    let b = RequestBuilder::new(client, url, Method::GET);
 
-    for id in ids {
-        b = b.with_query("id", id.to_string());
-    }
-
-    for login in logins {
-        b = b.with_query("login", login.to_string());
-    }
-
-    return b;
+    return b
+        .with_batched_query("id", ids.iter().map(|id| id.to_string()))
+        .with_batched_query("login", logins.iter().map(|login| login.to_string()));
 }
 
 /**
@@ -2,7 +2,7 @@ use twitch_types::{GameId, UserId};
 
 use crate::client::{DefaultOpts, RequestBuilder};
 
-use super::models::{ApiError, PaginationContainer, Video};
+use super::models::{ApiError, DataContainer, PaginationContainer, Video};
 use super::*;
 
 pub struct Videos {}
@@ -36,7 +36,10 @@ impl<T, E> RequestBuilder<T, E, Videos> {
 }
 
 impl VideosNamespace {
-    pub fn by_id<S: ToString>(self, ids: &[S]) -> RequestBuilder<PaginationContainer<Video>> {
+    ///Get a list of videos by their id
+    ///
+    ///Lists longer than 100 ids are split into several requests
+    pub fn by_id<S: ToString>(self, ids: &[S]) -> RequestBuilder<DataContainer<Video>> {
         by_id(self.client, ids)
     }
 
@@ -64,15 +67,11 @@ impl Client {
 pub fn by_id<S: ToString>(
     client: Client,
     ids: &[S],
-) -> RequestBuilder<PaginationContainer<Video>, ApiError, DefaultOpts> {
+) -> RequestBuilder<DataContainer<Video>, ApiError, DefaultOpts> {
     let url = client.inner.api_base_uri().to_owned() + &String::from("/videos");
-    let mut b = RequestBuilder::new(client.inner, url, Method::GET);
-
-    for id in ids {
-        b = b.with_query("id", id.to_string());
-    }
+    let b = RequestBuilder::new(client.inner, url, Method::GET);
 
-    b
+    b.with_batched_query("id", ids.iter().map(|id| id.to_string()))
 }
 
 pub fn by_user<'a, Id: Into<UserId<'a>>>(
@@ -0,0 +1,158 @@
+//! Requests to endpoints that have no typed wrapper yet
+//!
+//! Raw requests go through the same client as the typed endpoints, so they
+//! are authenticated, ratelimited and retried in the same way.
+//!
+//! ```no_run
+//! # use twitch_api::HelixClient;
+//! # use hyper::Method;
+//! # async fn run(client: HelixClient) -> Result<(), twitch_api::error::Error> {
+//! let schedule: serde_json::Value = client
+//!     .raw(Method::GET, "/schedule")
+//!     .query("broadcaster_id", "141981764")
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::IntoFuture;
+use std::pin::Pin;
+
+use futures::Future;
+use hyper::body::Bytes;
+use hyper::{Method, Request};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+
+use super::models::{ApiError, PaginationContainer};
+use super::Client;
+use crate::client::{
+    encode, Client as GenericClient, ClientTrait, ForwardPagination, IterableApiRequest,
+    RequestBuilder,
+};
+use crate::error::Error;
+
+pub struct RawRequestBuilder {
+    client: GenericClient,
+    method: Method,
+    url: String,
+    params: Vec<(String, String)>,
+    body: Option<Value>,
+}
+
+/* Responses of raw requests carry no pagination information of their own.
+ * Paginated endpoints are read through `PaginationContainer` instead */
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct Response<T>(T);
+
+impl<T> ForwardPagination for Response<T> {
+    fn cursor(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl Client {
+    /// Request `path` relative to the api base uri, e.g. `/schedule`.
+    ///
+    /// Each segment of the path is percent-encoded, so it must be given
+    /// unencoded and without a query. Use `query` for parameters
+    pub fn raw(&self, method: Method, path: &str) -> RawRequestBuilder {
+        let client = self.inner.clone();
+        let path: Vec<String> = path
+            .trim_start_matches('/')
+            .split('/')
+            .map(|segment| utf8_percent_encode(segment, PATH_SEGMENT_ENCODE_SET).collect())
+            .collect();
+        let url = client.api_base_uri().to_owned() + "/" + &path.join("/");
+
+        RawRequestBuilder {
+            client,
+            method,
+            url,
+            params: Vec::new(),
+            body: None,
+        }
+    }
+}
+
+impl RawRequestBuilder {
+    /// Add a query parameter. The value is percent-encoded
+    pub fn query<V: ToString>(mut self, key: &str, value: V) -> Self {
+        let value = value.to_string();
+        self.params.push((encode(key), encode(&value)));
+        self
+    }
+
+    /// Add the query parameter `key` once for each value, as Helix expects
+    /// for lists such as `id=1&id=2`
+    pub fn query_all<I: IntoIterator>(mut self, key: &str, values: I) -> Self
+    where
+        I::Item: ToString,
+    {
+        for value in values {
+            self = self.query(key, value);
+        }
+        self
+    }
+
+    /// Send `body` as the JSON body of the request
+    pub fn body(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Serialize `body` as the JSON body of the request
+    pub fn json<B: Serialize>(self, body: &B) -> Result<Self, Error> {
+        Ok(self.body(serde_json::to_value(body)?))
+    }
+
+    /// Send the request with the user access `token`. See `Client::as_user`
+    pub fn as_user(mut self, token: &str) -> Self {
+        self.client = self.client.as_user(token);
+        self
+    }
+
+    /// Perform the request and deserialize the response as `T`
+    pub async fn send<T: DeserializeOwned + Send + 'static>(self) -> Result<T, Error> {
+        let Response(value) = self.builder::<Response<T>>().await?;
+        Ok(value)
+    }
+
+    /// The http request `send` would perform. See `RequestBuilder::prepare`
+    pub fn prepare(self, redact: bool) -> Result<Request<Bytes>, Error> {
+        self.builder::<Response<Value>>().prepare(redact)
+    }
+
+    /// Read a paginated endpoint page by page. The items of each page are
+    /// deserialized as `T`
+    pub fn paginate<T: DeserializeOwned + Send + 'static>(
+        self,
+    ) -> IterableApiRequest<PaginationContainer<T>, ApiError> {
+        self.builder::<PaginationContainer<T>>().build_iterable()
+    }
+
+    fn builder<T: DeserializeOwned + ForwardPagination + Send + 'static>(
+        self,
+    ) -> RequestBuilder<T, ApiError> {
+        let mut builder = RequestBuilder::new(self.client, self.url, self.method);
+        for (key, value) in self.params {
+            builder = builder.with_query(key, value);
+        }
+        match self.body {
+            Some(body) => builder.with_body(body),
+            None => builder,
+        }
+    }
+}
+
+impl IntoFuture for RawRequestBuilder {
+    type Output = Result<Value, Error>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.send())
+    }
+}
@@ -0,0 +1,295 @@
+//! Persistent storage for access tokens
+//!
+//! A [`TokenStore`] keeps credentials across restarts. The client built by
+//! `AuthClientBuilder::token_store` and the [`TokenManager`] load their tokens
+//! from a store and write refreshed tokens back to it.
+//!
+//! [`TokenManager`]: super::tokens::TokenManager
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use super::models::Credentials;
+use crate::error::Error;
+
+/// Storage for credentials, keyed by a name chosen by the user of the store
+pub trait TokenStore: Send + Sync {
+    fn load<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Credentials>, Error>>;
+    fn save<'a>(
+        &'a self,
+        key: &'a str,
+        credentials: &'a Credentials,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), Error>>;
+    /// Keys of all stored credentials
+    fn keys(&self) -> BoxFuture<'_, Result<Vec<String>, Error>>;
+}
+
+type TokenMap = BTreeMap<String, Credentials>;
+
+/* Reads and writes of the whole file, with the encoding of the file */
+trait Codec: Send + Sync {
+    fn decode(&self, contents: &[u8]) -> Result<TokenMap, Error>;
+    fn encode(&self, tokens: &TokenMap) -> Result<Vec<u8>, Error>;
+}
+
+/* Tokens are kept in a single file that is replaced on every change. The
+ * lock serializes changes so none are lost */
+struct TokenFile<C> {
+    path: PathBuf,
+    codec: C,
+    lock: Mutex<()>,
+}
+
+impl<C: Codec> TokenFile<C> {
+    fn new(path: PathBuf, codec: C) -> TokenFile<C> {
+        TokenFile {
+            path,
+            codec,
+            lock: Mutex::new(()),
+        }
+    }
+
+    async fn read(&self) -> Result<TokenMap, Error> {
+        match tokio::fs::read(&self.path).await {
+            Ok(contents) => self.codec.decode(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(TokenMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /* Written to a temporary file first so that a crash never leaves a
+     * partially written store behind */
+    async fn write(&self, tokens: &TokenMap) -> Result<(), Error> {
+        let contents = self.codec.encode(tokens)?;
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+
+        let mut file = options.open(&tmp).await?;
+        file.write_all(&contents).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&tmp, &self.path).await?;
+        Ok(())
+    }
+
+    async fn load(&self, key: &str) -> Result<Option<Credentials>, Error> {
+        let _lock = self.lock.lock().await;
+        Ok(self.read().await?.remove(key))
+    }
+
+    async fn save(&self, key: &str, credentials: &Credentials) -> Result<(), Error> {
+        let _lock = self.lock.lock().await;
+        let mut tokens = self.read().await?;
+        tokens.insert(key.to_owned(), credentials.clone());
+        self.write(&tokens).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), Error> {
+        let _lock = self.lock.lock().await;
+        let mut tokens = self.read().await?;
+        if tokens.remove(key).is_some() {
+            self.write(&tokens).await?;
+        }
+        Ok(())
+    }
+
+    async fn keys(&self) -> Result<Vec<String>, Error> {
+        let _lock = self.lock.lock().await;
+        Ok(self.read().await?.into_keys().collect())
+    }
+}
+
+struct Json;
+
+impl Codec for Json {
+    fn decode(&self, contents: &[u8]) -> Result<TokenMap, Error> {
+        Ok(serde_json::from_slice(contents)?)
+    }
+
+    fn encode(&self, tokens: &TokenMap) -> Result<Vec<u8>, Error> {
+        Ok(serde_json::to_vec_pretty(tokens)?)
+    }
+}
+
+/// Stores tokens in a JSON file that only the owner can read and write
+pub struct JsonFileStore {
+    file: TokenFile<Json>,
+}
+
+impl JsonFileStore {
+    /// The file is created on the first save
+    pub fn new<P: AsRef<Path>>(path: P) -> JsonFileStore {
+        JsonFileStore {
+            file: TokenFile::new(path.as_ref().to_owned(), Json),
+        }
+    }
+}
+
+#[cfg(feature = "encrypted-store")]
+mod encrypted {
+    use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+    use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+    use super::*;
+
+    const NONCE_LEN: usize = 12;
+
+    pub(super) struct Encrypted {
+        cipher: ChaCha20Poly1305,
+    }
+
+    impl Codec for Encrypted {
+        fn decode(&self, contents: &[u8]) -> Result<TokenMap, Error> {
+            if contents.len() < NONCE_LEN {
+                return Err(Error::config_error("token store is corrupted"));
+            }
+            let (nonce, ciphertext) = contents.split_at(NONCE_LEN);
+            let json = self
+                .cipher
+                .decrypt(Nonce::from_slice(nonce), ciphertext)
+                .map_err(|_| Error::config_error("unable to decrypt token store"))?;
+            Json.decode(&json)
+        }
+
+        fn encode(&self, tokens: &TokenMap) -> Result<Vec<u8>, Error> {
+            let json = Json.encode(tokens)?;
+            let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+            let ciphertext = self
+                .cipher
+                .encrypt(&nonce, json.as_ref())
+                .map_err(|_| Error::config_error("unable to encrypt token store"))?;
+
+            let mut contents = nonce.to_vec();
+            contents.extend_from_slice(&ciphertext);
+            Ok(contents)
+        }
+    }
+
+    /// Stores tokens in a file encrypted with ChaCha20-Poly1305. Like
+    /// [`JsonFileStore`] only the owner can read and write the file
+    pub struct EncryptedFileStore {
+        pub(super) file: TokenFile<Encrypted>,
+    }
+
+    impl EncryptedFileStore {
+        /// `key` should be random and kept outside of the store's directory,
+        /// e.g. in a secret manager
+        pub fn new<P: AsRef<Path>>(path: P, key: [u8; 32]) -> EncryptedFileStore {
+            let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+            EncryptedFileStore {
+                file: TokenFile::new(path.as_ref().to_owned(), Encrypted { cipher }),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "encrypted-store")]
+pub use self::encrypted::EncryptedFileStore;
+
+macro_rules! delegate_store {
+    ($store:ty) => {
+        impl TokenStore for $store {
+            fn load<'a>(
+                &'a self,
+                key: &'a str,
+            ) -> BoxFuture<'a, Result<Option<Credentials>, Error>> {
+                self.file.load(key).boxed()
+            }
+
+            fn save<'a>(
+                &'a self,
+                key: &'a str,
+                credentials: &'a Credentials,
+            ) -> BoxFuture<'a, Result<(), Error>> {
+                self.file.save(key, credentials).boxed()
+            }
+
+            fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), Error>> {
+                self.file.delete(key).boxed()
+            }
+
+            fn keys(&self) -> BoxFuture<'_, Result<Vec<String>, Error>> {
+                self.file.keys().boxed()
+            }
+        }
+    };
+}
+
+delegate_store!(JsonFileStore);
+#[cfg(feature = "encrypted-store")]
+delegate_store!(EncryptedFileStore);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::HelixScope;
+
+    fn credentials(token: &str) -> Credentials {
+        let mut credentials = Credentials::new(token, Some("refresh".to_owned()));
+        credentials.expires_in = 3600;
+        credentials.scope = vec![HelixScope::UserReadEmail];
+        credentials
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn test_json_store_round_trip() {
+        let path = temp_path("twitch_api_tokens.json");
+        let store = JsonFileStore::new(&path);
+        assert!(store.load("app").await.unwrap().is_none());
+
+        store.save("app", &credentials("a")).await.unwrap();
+        store.save("user:1", &credentials("b")).await.unwrap();
+        assert_eq!(store.keys().await.unwrap(), vec!["app", "user:1"]);
+
+        /* A new store reads what the previous one wrote */
+        let store = JsonFileStore::new(&path);
+        assert_eq!(store.load("app").await.unwrap().unwrap().access_token, "a");
+        store.delete("app").await.unwrap();
+        assert!(store.load("app").await.unwrap().is_none());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "encrypted-store")]
+    #[tokio::test]
+    async fn test_encrypted_store_requires_key() {
+        let path = temp_path("twitch_api_tokens.enc");
+        let store = EncryptedFileStore::new(&path, [7; 32]);
+        store
+            .save("app", &credentials("secret_token"))
+            .await
+            .unwrap();
+
+        let contents = std::fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&contents).contains("secret_token"));
+
+        let store = EncryptedFileStore::new(&path, [7; 32]);
+        assert!(store.load("app").await.unwrap().is_some());
+        let store = EncryptedFileStore::new(&path, [8; 32]);
+        assert!(store.load("app").await.unwrap_err().is_config_error());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
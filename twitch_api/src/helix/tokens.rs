@@ -0,0 +1,385 @@
+//! Access tokens of many users
+//!
+//! A [`TokenManager`] holds the credentials of users by their id and hands
+//! out clients that perform requests as a user. Tokens are refreshed with the
+//! refresh grant when they are about to expire. Twitch requires tokens to be
+//! validated hourly; tokens that fail validation and can't be refreshed are
+//! removed and reported as [`TokenEvent::Revoked`].
+//!
+//! With a [`TokenStore`] all changes to the tokens are written to the store,
+//! and the tokens of a previous run are loaded with [`TokenManager::restore`].
+//!
+//! ```no_run
+//! # async fn run(credentials: twitch_api::helix::models::Credentials) -> Result<(), twitch_api::error::Error> {
+//! use twitch_api::helix::tokens::{TokenManager, VALIDATION_INTERVAL};
+//! use twitch_api::HelixClient;
+//!
+//! let client = HelixClient::new("client_id");
+//! let manager = TokenManager::new(&client, "secret");
+//! manager.spawn_validation(VALIDATION_INTERVAL);
+//!
+//! let user = manager.add(credentials).await?;
+//! let channel = manager.client(&user).await?.channels().channel(user).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+
+use tokio::sync::{broadcast, Mutex as AsyncMutex};
+use tokio::task::JoinHandle;
+use twitch_types::UserId;
+
+use super::models::Credentials;
+use super::token_store::TokenStore;
+use super::Client;
+use crate::error::Error;
+use crate::models::Message;
+
+/// Tokens that expire within this margin are refreshed before they are used
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Interval at which Twitch requires tokens to be validated
+pub const VALIDATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const EVENT_CAPACITY: usize = 64;
+
+/* Users' tokens are stored under their id with this prefix, so a store can
+ * also hold other tokens */
+const STORE_KEY_PREFIX: &str = "user:";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenEvent {
+    /// The user's token was replaced with a refreshed one
+    Refreshed(UserId<'static>),
+    /// The user's token is no longer valid and was removed
+    Revoked(UserId<'static>),
+}
+
+fn needs_refresh(credentials: &Credentials) -> bool {
+    let margin = chrono::Duration::from_std(REFRESH_MARGIN).unwrap();
+    match credentials.expires_at() {
+        Some(expires_at) => expires_at <= Utc::now() + margin,
+        None => false,
+    }
+}
+
+/* Each token is locked while it is refreshed so that concurrent users of a
+ * token wait for the refresh instead of spending the refresh token twice */
+type Entry = Arc<AsyncMutex<Credentials>>;
+
+struct Inner {
+    client: Client,
+    secret: String,
+    tokens: Mutex<HashMap<UserId<'static>, Entry>>,
+    events: broadcast::Sender<TokenEvent>,
+    store: Option<Arc<dyn TokenStore>>,
+}
+
+/// Credentials of many users. Cloning the manager is cheap and clones share
+/// their tokens
+#[derive(Clone)]
+pub struct TokenManager {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for TokenManager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TokenManager")
+            .field("users", &self.users())
+            .finish()
+    }
+}
+
+fn store_key(user: &UserId<'_>) -> String {
+    STORE_KEY_PREFIX.to_owned() + user.as_ref()
+}
+
+fn owned(user: &UserId<'_>) -> UserId<'static> {
+    UserId::from(user.as_ref().to_owned())
+}
+
+fn unknown_user(user: &UserId<'_>) -> Error {
+    Error::auth_error(Some(Message {
+        error: None,
+        message: format!("no token for user {}", user.as_ref()),
+        status: 401,
+    }))
+}
+
+impl TokenManager {
+    /// OAuth requests are made with `client`, which also shares its
+    /// connection pool and configuration with the clients of users. The
+    /// client `secret` is needed to refresh tokens
+    pub fn new<S: Into<String>>(client: &Client, secret: S) -> TokenManager {
+        TokenManager::create(client, secret.into(), None)
+    }
+
+    /// Like `new`, and keep the tokens in `store`
+    pub fn new_with_store<S: Into<String>>(
+        client: &Client,
+        secret: S,
+        store: Arc<dyn TokenStore>,
+    ) -> TokenManager {
+        TokenManager::create(client, secret.into(), Some(store))
+    }
+
+    fn create(client: &Client, secret: String, store: Option<Arc<dyn TokenStore>>) -> TokenManager {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        TokenManager {
+            inner: Arc::new(Inner {
+                client: client.clone(),
+                secret,
+                tokens: Mutex::new(HashMap::new()),
+                events,
+                store,
+            }),
+        }
+    }
+
+    /// Load the tokens saved in the store and validate them. Returns the
+    /// users with a usable token
+    pub async fn restore(&self) -> Result<Vec<UserId<'static>>, Error> {
+        let store = match &self.inner.store {
+            Some(store) => store,
+            None => return Ok(Vec::new()),
+        };
+
+        for key in store.keys().await? {
+            let user = match key.strip_prefix(STORE_KEY_PREFIX) {
+                Some(user) => UserId::from(user.to_owned()),
+                None => continue,
+            };
+            if let Some(credentials) = store.load(&key).await? {
+                let entry = Arc::new(AsyncMutex::new(credentials));
+                self.inner.tokens.lock().unwrap().insert(user, entry);
+            }
+        }
+
+        /* Tokens may have been revoked while they were stored */
+        self.validate().await;
+        Ok(self.users())
+    }
+
+    /// Validate `credentials` and add them for the user they belong to
+    pub async fn add(&self, mut credentials: Credentials) -> Result<UserId<'static>, Error> {
+        let validation = self
+            .inner
+            .client
+            .auth()
+            .validate(&credentials.access_token)
+            .await?;
+        let user = validation
+            .user_id
+            .ok_or_else(|| Error::config_error("not a user access token"))?;
+
+        credentials.set_expires_in(validation.expires_in);
+        credentials.scope = validation.scopes;
+        self.insert(user.clone(), credentials).await?;
+        Ok(user)
+    }
+
+    /// Add `credentials` for `user` without validating them. Replaces the
+    /// user's previous token
+    pub async fn insert(
+        &self,
+        user: UserId<'static>,
+        credentials: Credentials,
+    ) -> Result<(), Error> {
+        if let Some(store) = &self.inner.store {
+            store.save(&store_key(&user), &credentials).await?;
+        }
+        let entry = Arc::new(AsyncMutex::new(credentials));
+        self.inner.tokens.lock().unwrap().insert(user, entry);
+        Ok(())
+    }
+
+    /// Remove the token of `user`. Returns whether there was one
+    pub async fn remove(&self, user: &UserId<'_>) -> Result<bool, Error> {
+        if let Some(store) = &self.inner.store {
+            store.delete(&store_key(user)).await?;
+        }
+        Ok(self
+            .inner
+            .tokens
+            .lock()
+            .unwrap()
+            .remove(&owned(user))
+            .is_some())
+    }
+
+    pub fn users(&self) -> Vec<UserId<'static>> {
+        self.inner.tokens.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Receive refresh and revocation events from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<TokenEvent> {
+        self.inner.events.subscribe()
+    }
+
+    /// A client that performs requests as `user`. The token is refreshed
+    /// first when it is about to expire.
+    ///
+    /// The client keeps using the token it was created with, so handles
+    /// should be requested again rather than kept for long
+    pub async fn client(&self, user: &UserId<'_>) -> Result<Client, Error> {
+        let user = owned(user);
+        let entry = self.entry(&user).ok_or_else(|| unknown_user(&user))?;
+        let mut token = entry.lock().await;
+        if needs_refresh(&token) {
+            self.refresh_token(&user, &entry, &mut token).await?;
+        }
+
+        Ok(Client {
+            inner: self.inner.client.inner.as_user_with_scopes(
+                &user,
+                &token.access_token,
+                token.scope.clone(),
+            ),
+        })
+    }
+
+    /// Refresh the token of `user` regardless of its expiry
+    pub async fn refresh(&self, user: &UserId<'_>) -> Result<(), Error> {
+        let user = owned(user);
+        let entry = self.entry(&user).ok_or_else(|| unknown_user(&user))?;
+        let mut token = entry.lock().await;
+        self.refresh_token(&user, &entry, &mut token).await
+    }
+
+    /// Validate all tokens. Tokens that are no longer valid are refreshed,
+    /// or removed when that fails.
+    ///
+    /// Returns the users whose token was removed
+    pub async fn validate(&self) -> Vec<UserId<'static>> {
+        let entries: Vec<_> = {
+            let tokens = self.inner.tokens.lock().unwrap();
+            tokens
+                .iter()
+                .map(|(user, entry)| (user.clone(), entry.clone()))
+                .collect()
+        };
+
+        let mut revoked = Vec::new();
+        for (user, entry) in entries {
+            let mut token = entry.lock().await;
+            let validation = self.inner.client.auth().validate(&token.access_token).await;
+
+            let result = match validation {
+                Ok(validation) => {
+                    token.set_expires_in(validation.expires_in);
+                    Ok(())
+                }
+                Err(e) if e.is_token_rejected() => {
+                    self.refresh_token(&user, &entry, &mut token).await
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Err(e) if e.is_token_rejected() => revoked.push(user),
+                Err(e) => warn!("unable to validate token of {:?}: {}", user, e),
+                Ok(()) => {}
+            }
+        }
+        revoked
+    }
+
+    /// Validate all tokens every `interval` until the manager and all its
+    /// clones are dropped
+    pub fn spawn_validation(&self, interval: Duration) -> JoinHandle<()> {
+        let inner = Arc::downgrade(&self.inner);
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            /* The first tick completes immediately */
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                match inner.upgrade() {
+                    Some(inner) => {
+                        TokenManager { inner }.validate().await;
+                    }
+                    None => break,
+                }
+            }
+        })
+    }
+
+    fn entry(&self, user: &UserId<'static>) -> Option<Entry> {
+        self.inner.tokens.lock().unwrap().get(user).cloned()
+    }
+
+    async fn refresh_token(
+        &self,
+        user: &UserId<'static>,
+        entry: &Entry,
+        token: &mut Credentials,
+    ) -> Result<(), Error> {
+        let refresh_token = match &token.refresh_token {
+            Some(refresh_token) => refresh_token.clone(),
+            None => {
+                self.revoke(user, entry).await;
+                return Err(Error::auth_error(None));
+            }
+        };
+
+        let refreshed = self
+            .inner
+            .client
+            .auth()
+            .refresh_token(&self.inner.secret, &refresh_token)
+            .await;
+        match refreshed {
+            Ok(credentials) => {
+                /* The refreshed token is in use regardless, so a failure to
+                 * save it must not fail the request it was refreshed for */
+                if let Some(store) = &self.inner.store {
+                    if let Err(e) = store.save(&store_key(user), &credentials).await {
+                        warn!("unable to save token of {:?}: {}", user, e);
+                    }
+                }
+                *token = credentials;
+                self.emit(TokenEvent::Refreshed(user.clone()));
+                Ok(())
+            }
+            Err(e) => {
+                if e.is_token_rejected() {
+                    self.revoke(user, entry).await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /* Only the entry that failed is removed, a token inserted for the user
+     * in the meantime is kept */
+    async fn revoke(&self, user: &UserId<'static>, entry: &Entry) {
+        let removed = {
+            let mut tokens = self.inner.tokens.lock().unwrap();
+            match tokens.get(user) {
+                Some(current) if Arc::ptr_eq(current, entry) => tokens.remove(user).is_some(),
+                _ => false,
+            }
+        };
+        if !removed {
+            return;
+        }
+
+        if let Some(store) = &self.inner.store {
+            if let Err(e) = store.delete(&store_key(user)).await {
+                warn!("unable to delete token of {:?}: {}", user, e);
+            }
+        }
+        self.emit(TokenEvent::Revoked(user.clone()));
+    }
+
+    fn emit(&self, event: TokenEvent) {
+        /* Sending only fails when nobody is subscribed */
+        let _ = self.inner.events.send(event);
+    }
+}
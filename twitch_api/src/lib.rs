@@ -8,11 +8,15 @@ extern crate serde_derive;
 extern crate log;
 extern crate twitch_types;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
+pub mod connector;
+pub mod env;
 pub mod error;
 pub mod helix;
 pub mod models;
 pub mod namespace;
 
-pub use self::client::ClientConfig;
+pub use self::client::{ClientConfig, ClientConfigBuilder};
 pub use self::helix::Client as HelixClient;
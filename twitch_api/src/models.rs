@@ -1,15 +1,8 @@
 extern crate serde_json;
 
-use crate::{
-    client::{ForwardPagination, HelixScope},
-    helix::models::ApiError,
-};
+use crate::{client::ForwardPagination, helix::models::ApiError};
 
-impl ForwardPagination for Credentials {
-    fn cursor<'a>(&'a self) -> Option<&'a str> {
-        None
-    }
-}
+pub use crate::helix::models::Credentials;
 
 impl ForwardPagination for Message {
     fn cursor<'a>(&'a self) -> Option<&'a str> {
@@ -17,15 +10,6 @@ impl ForwardPagination for Message {
     }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct Credentials {
-    pub access_token: String,
-    pub refresh_token: Option<String>,
-    pub expires_in: u32,
-    pub scope: Option<Vec<HelixScope>>,
-    pub token_type: String,
-}
-
 #[derive(Debug, Deserialize)]
 pub struct Message {
     pub error: Option<String>,
@@ -1,6 +1,6 @@
 use crate::client::ClientTrait;
 use crate::client::{Client, RequestBuilder};
-use crate::helix::models::Credentials;
+use crate::helix::models::{Credentials, TokenValidation};
 use hyper::Method;
 use std::marker::PhantomData;
 
@@ -54,3 +54,31 @@ pub fn client_credentials<S: Into<String>>(
 
     b
 }
+
+/**
+ * https://dev.twitch.tv/docs/authentication/refresh-tokens/
+*/
+pub fn refresh_token<S: Into<String>>(
+    client: Client,
+    secret: S,
+    token: &str,
+) -> RequestBuilder<Credentials> {
+    let url = client.auth_base_uri().to_owned() + "/token";
+    let client_id = client.id().to_owned();
+
+    RequestBuilder::new(client, url, Method::POST)
+        .with_query("client_id", client_id)
+        .with_query("client_secret", secret)
+        .with_query("grant_type", "refresh_token")
+        .with_query("refresh_token", token)
+}
+
+/**
+ * https://dev.twitch.tv/docs/authentication/validate-tokens/
+*/
+pub fn validate(client: Client, token: &str) -> RequestBuilder<TokenValidation> {
+    let url = client.auth_base_uri().to_owned() + "/validate";
+
+    /* Validation must reflect the current state of the token */
+    RequestBuilder::new(client.with_token(token), url, Method::GET).coalesce(false)
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{user, StubServer};
+use hyper::StatusCode;
+use serde_json::json;
+use twitch_api::HelixClient;
+
+#[tokio::test]
+async fn test_users_split_into_batches() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|req| {
+        let users: Vec<_> = req
+            .query
+            .iter()
+            .filter(|(k, _)| k == "id" || k == "login")
+            .map(|(_, v)| user(v))
+            .collect();
+        assert!(users.len() <= 100);
+        (StatusCode::OK, json!({ "data": users }))
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let mut ids: Vec<_> = (0..230).map(|i| i.to_string()).collect();
+    ids.extend((0..20).map(|i| i.to_string()));
+    let logins = ["a", "b", "a"];
+
+    let users = client.users().users(&ids, &logins).await?;
+    assert_eq!(users.data.len(), 232);
+    assert_eq!(users.data[0].id, *"0");
+    assert_eq!(users.data[229].id, *"229");
+    assert_eq!(users.data[231].id, *"b");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    let mut sizes: Vec<_> = requests.iter().map(|r| r.query.len()).collect();
+    sizes.sort();
+    assert_eq!(sizes, [32, 100, 100]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_small_lookup_is_single_request() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|_| (StatusCode::OK, json!({ "data": [user("1")] }))).await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let users = client.users().users(&["1", "1"], &[] as &[&str]).await?;
+    assert_eq!(users.data.len(), 1);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].query, [("id".to_owned(), "1".to_owned())]);
+    Ok(())
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use std::error::Error;
+
+use chrono::{TimeZone, Utc};
+use common::stub_server::{paginate, StubServer};
+use hyper::StatusCode;
+use serde_json::{json, Value};
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::helix::models::{CheermoteType, ImageFormat, ImageScale, Theme};
+use twitch_api::helix::namespaces::bits::LeaderboardPeriod;
+use twitch_api::HelixClient;
+
+fn images(theme: &str, format: &str, extension: &str) -> Value {
+    let scales = ["1", "1.5", "2", "3", "4"];
+    let urls = scales.iter().map(|scale| {
+        let url = format!(
+            "https://d3aqoihi2n8ty8.cloudfront.net/actions/cheer/{}/{}/1/{}.{}",
+            theme, format, scale, extension
+        );
+        (scale.to_string(), json!(url))
+    });
+    Value::Object(urls.collect())
+}
+
+fn cheermote() -> Value {
+    let themed = |theme| json!({ "animated": images(theme, "animated", "gif"), "static": images(theme, "static", "png") });
+    json!({
+        "prefix": "Cheer",
+        "tiers": [{
+            "min_bits": 1,
+            "id": "1",
+            "color": "#979797",
+            "images": { "dark": themed("dark"), "light": themed("light") },
+            "can_cheer": true,
+            "show_in_bits_card": true
+        }],
+        "type": "global_first_party",
+        "order": 1,
+        "last_updated": "2018-05-22T00:06:04Z",
+        "is_charitable": false
+    })
+}
+
+fn transaction(id: usize) -> Value {
+    json!({
+        "id": format!("74c52265-e214-48a6-91b9-23b6014e8041-{}", id),
+        "timestamp": "2019-01-28T04:15:53.325Z",
+        "broadcaster_id": "439964613",
+        "broadcaster_login": "chikachi",
+        "broadcaster_name": "chikachi",
+        "user_id": "424596340",
+        "user_login": "quotrok",
+        "user_name": "quotrok",
+        "product_type": "BITS_IN_EXTENSION",
+        "product_data": {
+            "domain": "twitch.ext.uo6dggojyb8d6soh92zknwmi5ej1q2",
+            "sku": "testSku100",
+            "cost": { "amount": 100, "type": "bits" },
+            "inDevelopment": false,
+            "displayName": "Test Product 100",
+            "expiration": "",
+            "broadcast": false
+        }
+    })
+}
+
+async fn setup() -> (StubServer, HelixClient) {
+    let transactions: Vec<_> = (0..3).map(transaction).collect();
+    let server = StubServer::start(move |req| match req.path.as_str() {
+        "/bits/leaderboard" => (
+            StatusCode::OK,
+            json!({
+                "data": [
+                    { "user_id": "158010205", "user_login": "tundracowboy", "user_name": "TundraCowboy", "rank": 1, "score": 12543 },
+                    { "user_id": "7168163", "user_login": "topramens", "user_name": "Topramens", "rank": 2, "score": 6900 }
+                ],
+                "date_range": { "started_at": "2018-02-05T08:00:00Z", "ended_at": "2018-02-12T08:00:00Z" },
+                "total": 2
+            }),
+        ),
+        "/bits/cheermotes" => (StatusCode::OK, json!({ "data": [cheermote()] })),
+        _ => (StatusCode::OK, paginate(&transactions, req)),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_bits_leaderboard() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let leaderboard = client
+        .bits()
+        .leaderboard()
+        .count(2)
+        .period(LeaderboardPeriod::Week)
+        .started_at(Utc.with_ymd_and_hms(2018, 2, 5, 8, 0, 0).unwrap())
+        .user("158010205")
+        .await?;
+    assert_eq!(leaderboard.total, 2);
+    assert_eq!(leaderboard.data[0].rank, 1);
+    assert_eq!(leaderboard.data[0].score, 12543);
+    let range = leaderboard.date_range.unwrap();
+    assert_eq!(
+        range.ended_at,
+        Some(Utc.with_ymd_and_hms(2018, 2, 12, 8, 0, 0).unwrap())
+    );
+
+    let request = &server.requests()[0];
+    assert_eq!(request.param("count"), Some("2"));
+    assert_eq!(request.param("period"), Some("week"));
+    assert_eq!(request.param("started_at"), Some("2018-02-05T08:00:00Z"));
+    assert_eq!(request.param("user_id"), Some("158010205"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cheermotes() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let cheermotes = client.bits().cheermotes().broadcaster("41245072").await?;
+    let cheer = &cheermotes.data[0];
+    assert_eq!(cheer.cheermote_type, CheermoteType::GlobalFirstParty);
+    assert_eq!(
+        cheer.tiers[0]
+            .image(Theme::Light, ImageFormat::Static, ImageScale::X1_5)
+            .as_str(),
+        "https://d3aqoihi2n8ty8.cloudfront.net/actions/cheer/light/static/1/1.5.png"
+    );
+    assert_eq!(
+        cheer.tiers[0]
+            .image(Theme::Dark, ImageFormat::Animated, ImageScale::X4)
+            .as_str(),
+        "https://d3aqoihi2n8ty8.cloudfront.net/actions/cheer/dark/animated/1/4.gif"
+    );
+
+    client.bits().cheermotes().await?;
+    let requests = server.requests();
+    assert_eq!(requests[0].param("broadcaster_id"), Some("41245072"));
+    assert!(requests[1].query.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_extension_transactions() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client
+        .bits()
+        .extension_transactions("uo6dggojyb8d6soh92zknwmi5ej1q2")
+        .first(2)
+        .build_iterable()
+        .await?;
+    assert_eq!(first.data[0].product_data.cost.amount, 100);
+    assert_eq!(first.data[0].product_data.display_name, "Test Product 100");
+
+    let next = first.next().unwrap().await?;
+    assert_eq!(next.data.len(), 1);
+
+    let requests = server.requests();
+    assert!(requests.iter().all(|r| r.path == "/extensions/transactions"
+        && r.param("extension_id") == Some("uo6dggojyb8d6soh92zknwmi5ej1q2")));
+    assert_eq!(requests[1].param("after"), Some("2"));
+    Ok(())
+}
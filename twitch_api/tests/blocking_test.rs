@@ -0,0 +1,125 @@
+#![cfg(feature = "blocking")]
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{paginate, stream, user, StubServer};
+use hyper::{Method, StatusCode};
+use serde_json::json;
+use twitch_api::blocking::HelixClient;
+
+fn setup() -> (tokio::runtime::Runtime, StubServer, HelixClient) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let streams: Vec<_> = (0..5).map(stream).collect();
+    let server = runtime.block_on(StubServer::start(move |req| match req.path.as_str() {
+        "/users" => (StatusCode::OK, json!({ "data": [user("1")] })),
+        "/subscriptions/user" => (
+            StatusCode::NOT_FOUND,
+            json!({ "error": "Not Found", "status": 404, "message": "no subscription" }),
+        ),
+        _ if req.method == Method::POST => (StatusCode::OK, json!({ "data": [] })),
+        _ if req.method == Method::PATCH => (StatusCode::NO_CONTENT, json!(null)),
+        _ => (StatusCode::OK, paginate(&streams, req)),
+    }));
+
+    let config = common::stub_config(&server.base_uri);
+    let client = HelixClient::new_with_config("id", config).unwrap();
+    (runtime, server, client)
+}
+
+#[test]
+fn test_blocking_request() -> Result<(), Box<dyn Error>> {
+    let (_runtime, _server, client) = setup();
+
+    let users = client.users().users(&["1"], &[] as &[&str]).send()?;
+    assert_eq!(users.data[0].id, *"1");
+    Ok(())
+}
+
+#[test]
+fn test_blocking_pagination() -> Result<(), Box<dyn Error>> {
+    let (_runtime, server, client) = setup();
+
+    let mut page = client.streams().get().first(2).send_iterable()?;
+    let mut count = page.data.len();
+    while let Some(next) = client.next_page(&page)? {
+        count += next.data.len();
+        page = next;
+    }
+    assert_eq!(count, 5);
+    assert_eq!(server.requests().len(), 3);
+
+    let prev = client.prev_page(&page)?.unwrap();
+    assert_eq!(prev.data[0].id, *"2");
+    Ok(())
+}
+
+#[test]
+fn test_blocking_builder() -> Result<(), Box<dyn Error>> {
+    let (_runtime, server, client) = setup();
+
+    let marker = client
+        .streams()
+        .create_marker("141981764")
+        .map(|b| b.description("highlight"));
+    assert!(marker.as_async().validate().is_ok());
+    marker.send()?;
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, "/streams/markers");
+    Ok(())
+}
+
+#[test]
+fn test_blocking_channel_update() -> Result<(), Box<dyn Error>> {
+    let (_runtime, server, client) = setup();
+
+    client
+        .as_user("valid")
+        .channels()
+        .update("41245072")
+        .title("New title")
+        .send()?;
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, Method::PATCH);
+    assert_eq!(request.body, Some(json!({ "title": "New title" })));
+    Ok(())
+}
+
+#[test]
+fn test_blocking_subscription_check() -> Result<(), Box<dyn Error>> {
+    let (_runtime, server, client) = setup();
+
+    let subscription = client
+        .subscriptions()
+        .check("141981764", "1")
+        .as_user("user_token")
+        .send()?;
+    assert!(subscription.is_none());
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.authorization.as_deref(), Some("Bearer user_token"));
+    assert_eq!(request.param("user_id"), Some("1"));
+    Ok(())
+}
+
+#[test]
+fn test_blocking_endpoint_options() -> Result<(), Box<dyn Error>> {
+    let (_runtime, _server, client) = setup();
+
+    /* Options are available on the blocking builder, anything else through
+     * the asynchronous builder */
+    let request = client
+        .videos()
+        .for_game("33214")
+        .period("week")
+        .map(|b| b.with_query("first", "5"))
+        .prepare(true)?;
+    assert_eq!(
+        request.uri().query(),
+        Some("game_id=33214&period=week&first=5")
+    );
+    Ok(())
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::stub_server::{paginate, stream, user, StubServer};
+use futures::future::{ready, BoxFuture};
+use hyper::body::Bytes;
+use hyper::StatusCode;
+use serde_json::json;
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::helix::cache::{CacheConfig, ResponseCache};
+use twitch_api::HelixClient;
+
+async fn setup(cache: CacheConfig) -> (StubServer, HelixClient) {
+    let streams: Vec<_> = (0..5).map(stream).collect();
+    let server = StubServer::start(move |req| match req.path.as_str() {
+        "/users" => (StatusCode::OK, json!({ "data": [user("1")] })),
+        _ => (StatusCode::OK, paginate(&streams, req)),
+    })
+    .await;
+
+    let config = common::stub_builder(&server.base_uri)
+        .cache(cache)
+        .build()
+        .unwrap();
+    let client = HelixClient::new_with_config("id", config);
+    (server, client)
+}
+
+/// Store that records the keys it is given and never returns a response
+#[derive(Clone, Default)]
+struct KeyRecorder {
+    keys: Arc<Mutex<Vec<String>>>,
+}
+
+impl ResponseCache for KeyRecorder {
+    fn get<'a>(&'a self, _key: &'a str) -> BoxFuture<'a, Option<Bytes>> {
+        Box::pin(ready(None))
+    }
+
+    fn put(&self, key: String, _value: Bytes, _ttl: Duration) -> BoxFuture<'_, ()> {
+        self.keys.lock().unwrap().push(key);
+        Box::pin(ready(()))
+    }
+
+    fn remove_prefix<'a>(&'a self, _prefix: &'a str) -> BoxFuture<'a, ()> {
+        Box::pin(ready(()))
+    }
+}
+
+#[tokio::test]
+async fn test_repeated_lookup_is_cached() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(CacheConfig::in_memory(16)).await;
+
+    let first = client.users().users(&["1"], &[] as &[&str]).await?;
+    let second = client.users().users(&["1"], &[] as &[&str]).await?;
+    assert_eq!(first.data[0].id, second.data[0].id);
+    assert_eq!(server.requests().len(), 1);
+
+    client.users().users(&["2"], &[] as &[&str]).await?;
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_key_includes_cursor() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(CacheConfig::in_memory(16)).await;
+
+    let first = client.streams().get().first(2).build_iterable().await?;
+    let _ = client.streams().get().first(2).build_iterable().await?;
+    let second = first.next().unwrap().await?;
+    assert_eq!(second.data[0].id, *"2");
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_entries_expire() -> Result<(), Box<dyn Error>> {
+    let cache = CacheConfig::in_memory(16).with_ttl("/users", Duration::from_millis(50));
+    let (server, client) = setup(cache).await;
+
+    client.users().users(&["1"], &[] as &[&str]).await?;
+    client.users().users(&["1"], &[] as &[&str]).await?;
+    assert_eq!(server.requests().len(), 1);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    client.users().users(&["1"], &[] as &[&str]).await?;
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uncached_requests_and_empty_pages() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(CacheConfig::in_memory(16)).await;
+
+    client
+        .users()
+        .users(&["1"], &[] as &[&str])
+        .cache(false)
+        .await?;
+    client
+        .users()
+        .users(&["1"], &[] as &[&str])
+        .cache(false)
+        .await?;
+    assert_eq!(server.requests().len(), 2);
+
+    let empty = client.streams().get().with_query("after", "5").await?;
+    assert!(empty.data.is_empty());
+    client.streams().get().with_query("after", "5").await?;
+    assert_eq!(server.requests().len(), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_key_has_stable_token_digest() -> Result<(), Box<dyn Error>> {
+    let store = KeyRecorder::default();
+    let (_server, client) = setup(CacheConfig::new(store.clone())).await;
+
+    client
+        .as_user("user_token")
+        .users()
+        .users(&["1"], &[] as &[&str])
+        .await?;
+
+    /* First 8 bytes of the SHA-256 of "user_token" */
+    let keys = store.keys.lock().unwrap();
+    assert_eq!(keys.len(), 1);
+    assert!(keys[0].ends_with(" 00bc55eb73a2e88e"));
+    assert!(!keys[0].contains("user_token"));
+    Ok(())
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{channel, oauth, StubServer};
+use hyper::{Method, StatusCode};
+use serde_json::{json, Value};
+use twitch_api::client::HelixScope;
+use twitch_api::error::ValidationError;
+use twitch_api::helix::cache::CacheConfig;
+use twitch_api::helix::models::ContentClassificationLabel;
+use twitch_api::HelixClient;
+
+async fn setup(scopes: Vec<HelixScope>) -> Result<(StubServer, HelixClient), Box<dyn Error>> {
+    let server = StubServer::start(|req| match req.path.as_str() {
+        "/channels" => (StatusCode::NO_CONTENT, Value::Null),
+        _ => oauth(req),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri))
+        .authenticate("secret")
+        .token("valid")
+        .scopes(scopes)
+        .build()
+        .await?;
+    Ok((server, client))
+}
+
+#[tokio::test]
+async fn test_update_channel() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(vec![HelixScope::ChannelManageBroadcast]).await?;
+
+    client
+        .channels()
+        .update("41245072")
+        .title("Science & Technology")
+        .game("509670")
+        .tags(&["English", "Science"])
+        .content_classification_label(ContentClassificationLabel::Gambling, true)
+        .branded_content(false)
+        .await?;
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, Method::PATCH);
+    assert_eq!(request.path, "/channels");
+    assert_eq!(request.param("broadcaster_id"), Some("41245072"));
+    assert_eq!(
+        request.body,
+        Some(json!({
+            "game_id": "509670",
+            "title": "Science & Technology",
+            "tags": ["English", "Science"],
+            "content_classification_labels": [{ "id": "Gambling", "is_enabled": true }],
+            "is_branded_content": false
+        }))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_channel_validation() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(vec![HelixScope::ChannelManageBroadcast]).await?;
+    let sent = server.requests().len();
+
+    let update = || client.channels().update("41245072");
+    let error = |result: Result<(), twitch_api::error::Error>| {
+        result.unwrap_err().get_validation_error().cloned()
+    };
+
+    assert_eq!(error(update().await), Some(ValidationError::NoChanges));
+    assert_eq!(
+        error(update().title("  ").await),
+        Some(ValidationError::EmptyTitle)
+    );
+    assert_eq!(
+        error(update().delay(901).await),
+        Some(ValidationError::DelayTooLong(901))
+    );
+    assert_eq!(
+        error(update().language("english").await),
+        Some(ValidationError::InvalidLanguage("english".to_owned()))
+    );
+    assert_eq!(
+        error(update().tags(&["a"; 11]).await),
+        Some(ValidationError::TooManyTags(11))
+    );
+    assert_eq!(
+        error(update().tags(&["two words"]).await),
+        Some(ValidationError::InvalidTag("two words".to_owned()))
+    );
+    assert_eq!(
+        error(
+            update()
+                .content_classification_label(ContentClassificationLabel::MatureGame, false)
+                .await
+        ),
+        Some(ValidationError::ReadOnlyLabel(
+            ContentClassificationLabel::MatureGame
+        ))
+    );
+    assert!(update().language("other").delay(900).validate().is_ok());
+
+    assert_eq!(server.requests().len(), sent);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_channel_requires_scope() -> Result<(), Box<dyn Error>> {
+    let (_server, client) = setup(vec![HelixScope::UserReadEmail]).await?;
+
+    let err = client
+        .channels()
+        .update("41245072")
+        .title("title")
+        .await
+        .unwrap_err();
+    assert!(err.is_validation_error());
+    assert_eq!(
+        err.get_validation_error(),
+        Some(&ValidationError::MissingScope(
+            HelixScope::ChannelManageBroadcast
+        ))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_channels_by_ids() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|req| {
+        let channels: Vec<_> = req
+            .query
+            .iter()
+            .filter(|(k, _)| k == "broadcaster_id")
+            .map(|(_, v)| channel(v))
+            .collect();
+        (StatusCode::OK, json!({ "data": channels }))
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let ids: Vec<_> = (0..150).collect();
+    let channels = client.channels().by_ids(&ids).await?;
+    assert_eq!(channels.data.len(), 150);
+    assert_eq!(channels.data[149].broadcaster_id, *"149");
+    assert_eq!(channels.data[0].tags, ["English"]);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests.iter().all(|r| r.path == "/channels"));
+    let mut sizes: Vec<_> = requests.iter().map(|r| r.query.len()).collect();
+    sizes.sort();
+    assert_eq!(sizes, [50, 100]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_invalidates_cached_channel() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|req| match req.method {
+        Method::PATCH => (StatusCode::NO_CONTENT, Value::Null),
+        _ => (StatusCode::OK, json!({ "data": [channel("41245072")] })),
+    })
+    .await;
+    let config = common::stub_builder(&server.base_uri)
+        .cache(CacheConfig::in_memory(16))
+        .build()?;
+    let client = HelixClient::new_with_config("id", config);
+
+    client.channels().channel("41245072").await?;
+    client.channels().by_ids(&["41245072", "1"]).await?;
+    client.channels().channel("41245072").await?;
+    assert_eq!(server.requests().len(), 2);
+
+    client
+        .as_user("valid")
+        .channels()
+        .update("41245072")
+        .title("New title")
+        .await?;
+    client.channels().channel("41245072").await?;
+    client.channels().by_ids(&["41245072", "1"]).await?;
+    assert_eq!(server.requests().len(), 5);
+    Ok(())
+}
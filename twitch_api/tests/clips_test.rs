@@ -0,0 +1,187 @@
+mod common;
+
+use std::error::Error;
+use std::future::IntoFuture;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{TimeZone, Utc};
+use common::stub_server::{clip, paginate, StubServer};
+use hyper::{Method, StatusCode};
+use serde_json::json;
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::helix::cache::CacheConfig;
+use twitch_api::HelixClient;
+
+fn created(id: &str) -> serde_json::Value {
+    json!({
+        "data": [{
+            "id": id,
+            "edit_url": format!("https://clips.twitch.tv/{}/edit", id)
+        }]
+    })
+}
+
+#[tokio::test]
+async fn test_create_and_wait_for_clip() -> Result<(), Box<dyn Error>> {
+    /* The clip shows up on the third poll */
+    let polls = Arc::new(AtomicUsize::new(0));
+    let counter = polls.clone();
+    let server = StubServer::start(move |req| match req.method {
+        Method::POST => (StatusCode::ACCEPTED, created("FiveWordsForClipSlug")),
+        _ if counter.fetch_add(1, Ordering::SeqCst) < 2 => (StatusCode::OK, json!({ "data": [] })),
+        _ => (
+            StatusCode::OK,
+            json!({ "data": [clip("FiveWordsForClipSlug")] }),
+        ),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let created = client
+        .clips()
+        .create("141981764", true)
+        .as_user("valid")
+        .await?;
+    let created = &created.data[0];
+    assert_eq!(created.id, *"FiveWordsForClipSlug");
+    assert_eq!(
+        created.edit_url.as_str(),
+        "https://clips.twitch.tv/FiveWordsForClipSlug/edit"
+    );
+
+    let clip = client
+        .clips()
+        .wait_for_clip(created.id.clone())
+        .await?
+        .unwrap();
+    assert_eq!(clip.id, *"FiveWordsForClipSlug");
+    assert_eq!(polls.load(Ordering::SeqCst), 3);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, Method::POST);
+    assert_eq!(requests[0].path, "/clips");
+    assert_eq!(requests[0].param("broadcaster_id"), Some("141981764"));
+    assert_eq!(requests[0].param("has_delay"), Some("true"));
+    assert_eq!(requests[0].authorization.as_deref(), Some("Bearer valid"));
+    assert!(requests[1..]
+        .iter()
+        .all(|request| request.param("id") == Some("FiveWordsForClipSlug")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wait_for_clip_with_cache() -> Result<(), Box<dyn Error>> {
+    let polls = Arc::new(AtomicUsize::new(0));
+    let counter = polls.clone();
+    let server = StubServer::start(move |_| match counter.fetch_add(1, Ordering::SeqCst) {
+        0 => (StatusCode::OK, json!({ "data": [] })),
+        _ => (StatusCode::OK, json!({ "data": [clip("clip")] })),
+    })
+    .await;
+    let config = common::stub_builder(&server.base_uri)
+        .cache(CacheConfig::in_memory(16))
+        .build()?;
+    let client = HelixClient::new_with_config("id", config);
+
+    /* A cached lookup of the clip must not hide it from the poll */
+    client.clips().by_clips(&["clip"]).await?;
+    let clip = client.clips().wait_for_clip("clip").await?.unwrap();
+    assert_eq!(clip.id, *"clip");
+    assert_eq!(polls.load(Ordering::SeqCst), 2);
+
+    client.clips().by_clips(&["clip"]).await?;
+    client.clips().by_clips(&["clip"]).await?;
+    assert_eq!(polls.load(Ordering::SeqCst), 3);
+    client.clips().wait_for_clip("clip").await?;
+    assert_eq!(polls.load(Ordering::SeqCst), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clip_creation_has_own_ratelimit() -> Result<(), Box<dyn Error>> {
+    /* Every response exhausts the clip creation bucket until the next minute */
+    let reset = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 60;
+    let headers = vec![
+        ("ratelimit-helixclipscreation-limit", "600".to_owned()),
+        ("ratelimit-helixclipscreation-remaining", "0".to_owned()),
+        ("ratelimit-reset", reset.to_string()),
+    ];
+    let server = StubServer::start_with_headers(headers, |req| match req.method {
+        Method::POST => (StatusCode::ACCEPTED, created("clip")),
+        _ => (StatusCode::OK, json!({ "data": [clip("clip")] })),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    let create = |token: &str| client.clips().create("1", false).as_user(token);
+
+    create("first").await?;
+    create("second").await?;
+    client.clips().by_clips(&["clip"]).as_user("first").await?;
+
+    let limited = create("first").into_future();
+    assert!(tokio::time::timeout(Duration::from_millis(200), limited)
+        .await
+        .is_err());
+    assert_eq!(server.requests().len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clips_by_game_paginated_and_filtered() -> Result<(), Box<dyn Error>> {
+    let clips: Vec<_> = (0..5).map(|id| clip(&id.to_string())).collect();
+    let server = StubServer::start(move |req| (StatusCode::OK, paginate(&clips, req))).await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let started_at = Utc.with_ymd_and_hms(2023, 5, 1, 12, 30, 45).unwrap();
+    let first = client
+        .clips()
+        .by_game("33103")
+        .started_at(started_at)
+        .ended_at(started_at + chrono::Duration::days(7))
+        .is_featured(true)
+        .first(3)
+        .build_iterable()
+        .await?;
+    assert_eq!(first.data.len(), 3);
+    assert_eq!(first.data[0].game_id, *"33103");
+    assert!(!first.data[0].is_featured);
+
+    let second = first.next().unwrap().await?;
+    assert_eq!(second.data[1].id, *"4");
+    assert!(second.next().is_none());
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.param("game_id"), Some("33103"));
+    assert_eq!(request.param("started_at"), Some("2023-05-01T12:30:45Z"));
+    assert_eq!(request.param("ended_at"), Some("2023-05-08T12:30:45Z"));
+    assert_eq!(request.param("is_featured"), Some("true"));
+    assert_eq!(request.param("after"), Some("3"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clips_by_ids_are_merged() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|req| {
+        let clips: Vec<_> = req
+            .query
+            .iter()
+            .filter(|(key, _)| key == "id")
+            .map(|(_, id)| clip(id))
+            .collect();
+        (
+            StatusCode::OK,
+            json!({ "data": clips, "pagination": { "cursor": "next" } }),
+        )
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let ids: Vec<_> = (0..150).map(|id| id.to_string()).collect();
+    let clips = client.clips().by_clips(&ids).await?;
+    assert_eq!(clips.data.len(), 150);
+    assert!(clips.pagination.is_none());
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::error::Error;
+use std::future::IntoFuture;
+use std::time::Duration;
+
+use common::stub_server::{user, StubServer};
+use futures::future::join_all;
+use hyper::StatusCode;
+use serde_json::json;
+use twitch_api::HelixClient;
+
+async fn setup(status: StatusCode) -> (StubServer, HelixClient) {
+    let server = StubServer::start_delayed(Duration::from_millis(100), move |_| match status {
+        StatusCode::OK => (status, json!({ "data": [user("1")] })),
+        _ => (
+            status,
+            json!({ "error": "Internal Server Error", "status": 500, "message": "" }),
+        ),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_concurrent_lookups_share_request() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(StatusCode::OK).await;
+
+    let lookups = (0..5).map(|_| client.users().users(&["1"], &[] as &[&str]).into_future());
+    for res in join_all(lookups).await {
+        assert_eq!(res?.data[0].id, *"1");
+    }
+    assert_eq!(server.requests().len(), 1);
+
+    client.users().users(&["1"], &[] as &[&str]).await?;
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_coalescing_can_be_disabled() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(StatusCode::OK).await;
+
+    let lookups = (0..3).map(|_| {
+        client
+            .users()
+            .users(&["1"], &[] as &[&str])
+            .coalesce(false)
+            .into_future()
+    });
+    for res in join_all(lookups).await {
+        res?;
+    }
+    assert_eq!(server.requests().len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_errors_are_shared() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(StatusCode::INTERNAL_SERVER_ERROR).await;
+
+    let lookups = (0..3).map(|_| client.users().users(&["1"], &[] as &[&str]).into_future());
+    for res in join_all(lookups).await {
+        let err = res.err().unwrap();
+        assert_eq!(err.get_api_error().unwrap().status, 500);
+    }
+    /* The first attempt is retried once */
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_abandoned_request_is_not_joined() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup(StatusCode::OK).await;
+
+    let lookup = client.users().users(&["1"], &[] as &[&str]).into_future();
+    assert!(tokio::time::timeout(Duration::from_millis(20), lookup)
+        .await
+        .is_err());
+
+    /* The abandoned request still completes and leaves the in-flight map */
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(server.requests().len(), 1);
+
+    client.users().users(&["1"], &[] as &[&str]).await?;
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_inflight_keys_hide_token() -> Result<(), Box<dyn Error>> {
+    let (_server, client) = setup(StatusCode::OK).await;
+
+    let lookup = client
+        .users()
+        .users(&["1"], &[] as &[&str])
+        .as_user("secret_token")
+        .into_future();
+    let lookup = tokio::spawn(lookup);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    let debug = format!("{:?}", client);
+    assert!(debug.contains("/users?id=1"));
+    assert!(!debug.contains("secret_token"));
+    lookup.await??;
+    Ok(())
+}
@@ -3,12 +3,11 @@ use std::error::Error;
 use hyper::client::Client as HyperClient;
 use hyper::{client::HttpConnector, Request};
 use hyper::{Body, Method};
-use hyper_tls::HttpsConnector;
 use serde_derive::{Deserialize, Serialize};
 
 pub struct MockClient {
     pub base_uri: String,
-    pub hyper: HyperClient<HttpsConnector<HttpConnector>>,
+    pub hyper: HyperClient<HttpConnector>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -32,8 +31,7 @@ pub struct Container<T> {
 
 impl MockClient {
     pub fn build() -> Self {
-        let https = HttpsConnector::new();
-        let hyper = HyperClient::builder().build::<_, Body>(https);
+        let hyper = HyperClient::builder().build::<_, Body>(HttpConnector::new());
 
         MockClient {
             base_uri: "http://localhost:8080/units".to_string(),
@@ -1,10 +1,11 @@
 use std::error::Error;
 
-use twitch_api::ClientConfig;
+use twitch_api::{ClientConfig, ClientConfigBuilder};
 
 use self::mock_client::MockClient;
 
 pub mod mock_client;
+pub mod stub_server;
 
 pub struct TestContext {
     pub config: ClientConfig,
@@ -12,14 +13,24 @@ pub struct TestContext {
 }
 
 pub fn setup() -> Result<TestContext, Box<dyn Error>> {
-    let config = ClientConfig {
-        api_base_uri: "http://localhost:8080/mock".to_string(),
-        auth_base_uri: "http://localhost:8080/auth".to_string(),
-        ..ClientConfig::default()
-    };
+    let config = ClientConfig::builder()
+        .api_base_uri("http://localhost:8080/mock")
+        .auth_base_uri("http://localhost:8080/auth")
+        .build()?;
 
     Ok(TestContext {
         mock_client: MockClient::build(),
         config,
     })
 }
+
+/// Configuration that directs all requests to a stand-in server
+pub fn stub_builder(base_uri: &str) -> ClientConfigBuilder {
+    ClientConfig::builder()
+        .api_base_uri(base_uri)
+        .auth_base_uri(base_uri)
+}
+
+pub fn stub_config(base_uri: &str) -> ClientConfig {
+    stub_builder(base_uri).build().unwrap()
+}
@@ -0,0 +1,314 @@
+//! A minimal stand-in for the Helix API
+//!
+//! Unlike the twitch-cli mock, the stand-in runs in-process, records every
+//! request it receives and lets each test decide how to respond.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode, Version};
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub version: Version,
+    /// Host of the request target when it was sent in absolute form
+    pub authority: Option<String>,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub authorization: Option<String>,
+    /// JSON body of the request
+    pub body: Option<Value>,
+}
+
+impl RecordedRequest {
+    /// First value of a query parameter
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+type Handler = dyn Fn(&RecordedRequest) -> (StatusCode, Value) + Send + Sync;
+
+pub struct StubServer {
+    pub base_uri: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl StubServer {
+    pub async fn start<F>(handler: F) -> StubServer
+    where
+        F: Fn(&RecordedRequest) -> (StatusCode, Value) + Send + Sync + 'static,
+    {
+        StubServer::start_delayed(Duration::from_secs(0), handler).await
+    }
+
+    /// Start a server that waits `delay` before answering each request
+    pub async fn start_delayed<F>(delay: Duration, handler: F) -> StubServer
+    where
+        F: Fn(&RecordedRequest) -> (StatusCode, Value) + Send + Sync + 'static,
+    {
+        StubServer::serve(delay, Vec::new(), handler).await
+    }
+
+    /// Start a server that adds `headers` to every response
+    pub async fn start_with_headers<F>(
+        headers: Vec<(&'static str, String)>,
+        handler: F,
+    ) -> StubServer
+    where
+        F: Fn(&RecordedRequest) -> (StatusCode, Value) + Send + Sync + 'static,
+    {
+        StubServer::serve(Duration::from_secs(0), headers, handler).await
+    }
+
+    async fn serve<F>(
+        delay: Duration,
+        headers: Vec<(&'static str, String)>,
+        handler: F,
+    ) -> StubServer
+    where
+        F: Fn(&RecordedRequest) -> (StatusCode, Value) + Send + Sync + 'static,
+    {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let recorded = requests.clone();
+        let make_svc = make_service_fn(move |_conn| {
+            let recorded = recorded.clone();
+            let handler = handler.clone();
+            let headers = headers.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let recorded = recorded.clone();
+                    let handler = handler.clone();
+                    let headers = headers.clone();
+                    async move {
+                        let (parts, body) = req.into_parts();
+                        let body = hyper::body::to_bytes(body).await.unwrap();
+                        let request = RecordedRequest {
+                            method: parts.method.clone(),
+                            version: parts.version,
+                            authority: parts.uri.authority().map(|a| a.to_string()),
+                            path: parts.uri.path().to_owned(),
+                            query: url::form_urlencoded::parse(
+                                parts.uri.query().unwrap_or("").as_bytes(),
+                            )
+                            .into_owned()
+                            .collect(),
+                            authorization: parts
+                                .headers
+                                .get("Authorization")
+                                .and_then(|value| value.to_str().ok())
+                                .map(ToOwned::to_owned),
+                            body: serde_json::from_slice(&body).ok(),
+                        };
+                        let (status, body) = handler(&request);
+                        recorded.lock().unwrap().push(request);
+
+                        let mut response = Response::builder()
+                            .status(status)
+                            .header("Content-Type", "application/json");
+                        for (name, value) in &headers {
+                            response = response.header(*name, value.as_str());
+                        }
+                        let body = match status {
+                            StatusCode::NO_CONTENT => Body::empty(),
+                            _ => Body::from(body.to_string()),
+                        };
+                        let response = response.body(body).unwrap();
+                        tokio::time::sleep(delay).await;
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 0));
+        let server = Server::bind(&addr).serve(make_svc);
+        let base_uri = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+
+        StubServer { base_uri, requests }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Serve `items` as pages of a Helix collection.
+///
+/// Cursors are the offset into `items`. `after=c` returns the page starting
+/// at `c` and `before=c` the page ending at `c`. The returned cursor points
+/// past the page in the direction of travel.
+pub fn paginate(items: &[Value], request: &RecordedRequest) -> Value {
+    let first = request
+        .param("first")
+        .and_then(|f| f.parse::<usize>().ok())
+        .unwrap_or(20);
+    let parse = |c: &str| c.parse::<usize>().unwrap().min(items.len());
+
+    let (start, end, cursor) = if let Some(before) = request.param("before") {
+        let end = parse(before);
+        let start = end.saturating_sub(first);
+        (start, end, if start > 0 { Some(start) } else { None })
+    } else {
+        let start = request.param("after").map(parse).unwrap_or(0);
+        let end = (start + first).min(items.len());
+        (start, end, if end < items.len() { Some(end) } else { None })
+    };
+
+    let pagination = match cursor {
+        Some(cursor) => json!({ "cursor": cursor.to_string() }),
+        None => json!({}),
+    };
+
+    json!({
+        "data": &items[start..end],
+        "pagination": pagination,
+    })
+}
+
+pub fn stream(id: usize) -> Value {
+    json!({
+        "id": id.to_string(),
+        "user_id": "141981764",
+        "user_login": "twitchdev",
+        "user_name": "TwitchDev",
+        "game_id": "509670",
+        "game_name": "Science & Technology",
+        "type": "live",
+        "title": "Stream",
+        "viewer_count": id,
+        "started_at": "2021-03-10T15:04:21Z",
+        "language": "en",
+        "thumbnail_url": "https://static-cdn.jtvnw.net/previews-ttv/live_user_twitchdev-{width}x{height}.jpg",
+        "tag_ids": null,
+        "is_mature": false
+    })
+}
+
+pub fn user(id: &str) -> Value {
+    json!({
+        "id": id,
+        "login": format!("user{}", id),
+        "display_name": format!("User{}", id),
+        "type": "",
+        "broadcaster_type": "",
+        "description": "",
+        "profile_image_url": "https://static-cdn.jtvnw.net/jtv_user_pictures/profile_image-300x300.png",
+        "offline_image_url": "",
+        "view_count": 0
+    })
+}
+
+pub fn channel(id: &str) -> Value {
+    json!({
+        "broadcaster_id": id,
+        "broadcaster_login": format!("user{}", id),
+        "broadcaster_name": format!("User{}", id),
+        "broadcaster_language": "en",
+        "game_id": "509670",
+        "game_name": "Science & Technology",
+        "title": "Stream",
+        "delay": 0,
+        "tags": ["English"],
+        "content_classification_labels": [],
+        "is_branded_content": false
+    })
+}
+
+pub fn game(id: usize) -> Value {
+    json!({
+        "id": id.to_string(),
+        "name": format!("Game {}", id),
+        "box_art_url": format!("https://static-cdn.jtvnw.net/ttv-boxart/{}-{{width}}x{{height}}.jpg", id),
+        "igdb_id": (id + 1000).to_string()
+    })
+}
+
+pub fn clip(id: &str) -> Value {
+    json!({
+        "id": id,
+        "url": format!("https://clips.twitch.tv/{}", id),
+        "embed_url": format!("https://clips.twitch.tv/embed?clip={}", id),
+        "broadcaster_id": "1234",
+        "broadcaster_name": "JJ",
+        "creator_id": "123456",
+        "creator_name": "MrMarshall",
+        "video_id": "",
+        "game_id": "33103",
+        "language": "en",
+        "title": "random1",
+        "view_count": 10,
+        "created_at": "2017-11-30T22:34:18Z",
+        "thumbnail_url": "https://clips-media-assets.twitch.tv/157589949-preview-480x272.jpg",
+        "duration": 12.9,
+        "vod_offset": 1957,
+        "is_featured": false
+    })
+}
+
+/// Stand-in for the OAuth api and user lookups.
+///
+/// The tokens "valid" and "refreshed" pass validation, the refresh token
+/// "good" refreshes to "refreshed" and the client credentials grant issues
+/// "app_token"
+pub fn oauth(req: &RecordedRequest) -> (StatusCode, Value) {
+    let invalid = (
+        StatusCode::UNAUTHORIZED,
+        json!({ "status": 401, "message": "invalid access token" }),
+    );
+    match req.path.as_str() {
+        "/validate" => match req.authorization.as_deref() {
+            Some("Bearer app_token") => (
+                StatusCode::OK,
+                json!({ "client_id": "id", "scopes": [], "expires_in": 3600 }),
+            ),
+            Some("Bearer valid") | Some("Bearer refreshed") => (
+                StatusCode::OK,
+                json!({
+                    "client_id": "id",
+                    "login": "user",
+                    "user_id": "42",
+                    "scopes": ["user:read:email"],
+                    "expires_in": 3600
+                }),
+            ),
+            _ => invalid,
+        },
+        "/token" if req.param("grant_type") == Some("client_credentials") => (
+            StatusCode::OK,
+            json!({
+                "access_token": "app_token",
+                "expires_in": 3600,
+                "token_type": "bearer"
+            }),
+        ),
+        "/token" => match req.param("refresh_token") {
+            Some("good") => (
+                StatusCode::OK,
+                json!({
+                    "access_token": "refreshed",
+                    "refresh_token": "next",
+                    "expires_in": 3600,
+                    "scope": ["user:read:email"],
+                    "token_type": "bearer"
+                }),
+            ),
+            _ => (
+                StatusCode::BAD_REQUEST,
+                json!({ "error": "Bad Request", "status": 400, "message": "Invalid refresh token" }),
+            ),
+        },
+        _ => (StatusCode::OK, json!({ "data": [user("42")] })),
+    }
+}
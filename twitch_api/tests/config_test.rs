@@ -0,0 +1,161 @@
+mod common;
+
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+use common::stub_server::{user, StubServer};
+use hyper::{StatusCode, Version};
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use twitch_api::{ClientConfig, HelixClient};
+
+#[test]
+fn test_base_uris_are_validated() {
+    for uri in &[
+        "",
+        "api.twitch.tv/helix",
+        "ftp://api.twitch.tv",
+        "http://a/b?c=d",
+    ] {
+        let err = ClientConfig::builder()
+            .api_base_uri(*uri)
+            .build()
+            .unwrap_err();
+        assert!(err.is_config_error(), "{} was accepted", uri);
+    }
+
+    let err = ClientConfig::builder()
+        .proxy("https://proxy:3128")
+        .build()
+        .unwrap_err();
+    assert!(err.is_config_error());
+
+    let config = ClientConfig::builder()
+        .api_base_uri("http://localhost:8080/mock/")
+        .connect_timeout(Duration::from_secs(1))
+        .timeout(Duration::from_secs(2))
+        .read_timeout(Duration::from_secs(1))
+        .pool_max_idle_per_host(4)
+        .build()
+        .unwrap();
+    assert_eq!(config.api_base_uri, "http://localhost:8080/mock");
+    assert_eq!(config.timeout, Duration::from_secs(2));
+    assert_eq!(config.read_timeout, Some(Duration::from_secs(1)));
+}
+
+#[test]
+fn test_invalid_root_certificate() {
+    let err = ClientConfig::builder()
+        .add_root_certificate("not a certificate")
+        .build()
+        .unwrap_err();
+    assert!(err.is_config_error());
+}
+
+#[tokio::test]
+async fn test_requests_use_proxy() -> Result<(), Box<dyn Error>> {
+    let proxy = StubServer::start(|_| (StatusCode::OK, json!({ "data": [user("1")] }))).await;
+    let config = ClientConfig::builder()
+        .api_base_uri("http://api.twitch.invalid/helix")
+        .proxy(proxy.base_uri.as_str())
+        .build()?;
+    let client = HelixClient::new_with_config("id", config);
+
+    let users = client.users().users(&["1"], &[] as &[&str]).await?;
+    assert_eq!(users.data.len(), 1);
+
+    let requests = proxy.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].authority.as_deref(), Some("api.twitch.invalid"));
+    assert_eq!(requests[0].path, "/helix/users");
+    Ok(())
+}
+
+/* Serve responses whose body stops after the first bytes */
+async fn stalled_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_uri = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = [0; 1024];
+                let _ = stream.read(&mut request).await;
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\n{\"data\"")
+                    .await;
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            });
+        }
+    });
+    base_uri
+}
+
+#[tokio::test]
+async fn test_stalled_body_times_out() -> Result<(), Box<dyn Error>> {
+    let base_uri = stalled_server().await;
+    let client = |builder: twitch_api::client::ClientConfigBuilder| {
+        let config = builder
+            .api_base_uri(&base_uri)
+            .max_retrys(0)
+            .build()
+            .unwrap();
+        HelixClient::new_with_config("id", config)
+    };
+
+    let start = Instant::now();
+    let timeout = client(ClientConfig::builder().timeout(Duration::from_millis(300)));
+    assert!(timeout.streams().get().await.is_err());
+
+    let read_timeout = client(ClientConfig::builder().read_timeout(Duration::from_millis(300)));
+    assert!(read_timeout.streams().get().await.is_err());
+    assert!(start.elapsed() < Duration::from_secs(5));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_http2_only() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|_| (StatusCode::OK, json!({ "data": [user("1")] }))).await;
+    let config = common::stub_builder(&server.base_uri)
+        .http2_only(true)
+        .build()?;
+    let client = HelixClient::new_with_config("id", config);
+
+    client.users().users(&["1"], &[] as &[&str]).await?;
+    assert_eq!(server.requests()[0].version, Version::HTTP_2);
+    Ok(())
+}
+
+/* The TLS ClientHello a client sends for an https request */
+#[cfg(feature = "native-tls")]
+async fn client_hello(http2_only: bool) -> Vec<u8> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let config = ClientConfig::builder()
+        .api_base_uri(format!("https://{}", listener.local_addr().unwrap()))
+        .http2_only(http2_only)
+        .max_retrys(0)
+        .build()
+        .unwrap();
+    let client = HelixClient::new_with_config("id", config);
+    let request = tokio::spawn(async move { client.streams().get().await.is_err() });
+
+    let (mut stream, _) = listener.accept().await.unwrap();
+    let mut header = [0; 5];
+    stream.read_exact(&mut header).await.unwrap();
+    let mut hello = vec![0; u16::from_be_bytes([header[3], header[4]]) as usize];
+    stream.read_exact(&mut hello).await.unwrap();
+    drop(stream);
+
+    assert!(request.await.unwrap());
+    hello
+}
+
+#[cfg(feature = "native-tls")]
+#[tokio::test]
+async fn test_http2_only_requests_alpn() {
+    /* ALPN extension offering only "h2" */
+    let h2 = [0x00, 0x10, 0x00, 0x05, 0x00, 0x03, 0x02, b'h', b'2'];
+    let offers_h2 = |hello: &[u8]| hello.windows(h2.len()).any(|w| w == h2);
+
+    assert!(offers_h2(&client_hello(true).await));
+    assert!(!offers_h2(&client_hello(false).await));
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use std::env;
+use std::error::Error;
+
+use common::stub_server::{user, StubServer};
+use hyper::StatusCode;
+use serde_json::json;
+use serial_test::serial;
+use twitch_api::client::HelixScope;
+use twitch_api::{ClientConfig, HelixClient};
+
+const VARIABLES: &[&str] = &[
+    twitch_api::env::CLIENT_ID,
+    twitch_api::env::CLIENT_SECRET,
+    twitch_api::env::USER_TOKEN,
+    twitch_api::env::REFRESH_TOKEN,
+    twitch_api::env::SCOPES,
+    twitch_api::env::API_BASE_URI,
+    twitch_api::env::AUTH_BASE_URI,
+    twitch_api::env::PROXY,
+    twitch_api::env::TIMEOUT,
+    twitch_api::env::READ_TIMEOUT,
+    twitch_api::env::CONNECT_TIMEOUT,
+    twitch_api::env::MAX_RETRYS,
+];
+
+/* Values already in the environment, e.g. from .env, must not leak into tests */
+fn set_env(vars: &[(&str, &str)]) {
+    for name in VARIABLES {
+        env::set_var(name, "");
+    }
+    for (name, value) in vars {
+        env::set_var(name, value);
+    }
+}
+
+#[test]
+#[serial]
+fn test_config_from_env() {
+    set_env(&[
+        ("TWITCH_API_BASE_URI", "http://localhost:8080/mock/"),
+        ("TWITCH_TIMEOUT", "3"),
+        ("TWITCH_READ_TIMEOUT", "2"),
+    ]);
+    let config = ClientConfig::from_env().unwrap();
+    assert_eq!(config.api_base_uri, "http://localhost:8080/mock");
+    assert_eq!(config.timeout.as_secs(), 3);
+    assert_eq!(config.read_timeout.map(|t| t.as_secs()), Some(2));
+
+    set_env(&[("TWITCH_TIMEOUT", "soon")]);
+    assert!(ClientConfig::from_env().unwrap_err().is_config_error());
+}
+
+#[tokio::test]
+#[serial]
+async fn test_client_from_env_with_user_token() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|_| (StatusCode::OK, json!({ "data": [user("1")] }))).await;
+    set_env(&[
+        ("TWITCH_CLIENT_ID", "id"),
+        ("TWITCH_USER_TOKEN", "user_token"),
+        ("TWITCH_SCOPES", "user:read:email"),
+        ("TWITCH_API_BASE_URI", &server.base_uri),
+    ]);
+
+    let client = HelixClient::from_env().await?;
+    assert!(client.authenticated());
+    assert_eq!(client.scopes(), &[HelixScope::UserReadEmail]);
+
+    client.users().users(&["1"], &[] as &[&str]).await?;
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].authorization.as_deref(),
+        Some("Bearer user_token")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_client_from_env_with_secret() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|_| {
+        (
+            StatusCode::OK,
+            json!({
+                "access_token": "app_token",
+                "refresh_token": "",
+                "expires_in": 3600,
+                "scope": [],
+                "token_type": "bearer"
+            }),
+        )
+    })
+    .await;
+    set_env(&[
+        ("TWITCH_CLIENT_ID", "id"),
+        ("TWITCH_AUTH_BASE_URI", &server.base_uri),
+    ]);
+    assert!(HelixClient::from_env().await.unwrap_err().is_config_error());
+
+    env::set_var("TWITCH_CLIENT_SECRET", "secret");
+    let client = HelixClient::from_env().await?;
+    assert!(client.authenticated());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/token");
+    assert_eq!(requests[0].param("client_secret"), Some("secret"));
+    Ok(())
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{paginate, StubServer};
+use hyper::StatusCode;
+use serde_json::{json, Value};
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::HelixClient;
+
+fn follow(prefix: &str, id: usize) -> Value {
+    json!({
+        format!("{}_id", prefix): id.to_string(),
+        format!("{}_login", prefix): format!("user{}", id),
+        format!("{}_name", prefix): format!("User{}", id),
+        "followed_at": format!("2022-05-{:02}T10:00:00Z", 24 - id),
+    })
+}
+
+async fn setup() -> (StubServer, HelixClient) {
+    let followers: Vec<_> = (0..5).map(|id| follow("user", id)).collect();
+    let followed: Vec<_> = (0..3).map(|id| follow("broadcaster", id)).collect();
+    let server = StubServer::start(move |req| {
+        let items = match req.path.as_str() {
+            "/channels/followers" => &followers,
+            _ => &followed,
+        };
+        let mut page = paginate(items, req);
+        page["total"] = json!(items.len());
+        (StatusCode::OK, page)
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_channel_followers() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client
+        .channels()
+        .followers("141981764")
+        .first(3)
+        .build_iterable()
+        .await?;
+    assert_eq!(first.total, Some(5));
+    assert_eq!(first.data[0].user_id, *"0");
+    assert_eq!(
+        first.data[0].followed_at,
+        "2022-05-24T10:00:00Z".parse::<chrono::DateTime<chrono::Utc>>()?
+    );
+
+    let next = first.next().unwrap().await?;
+    assert_eq!(next.data.len(), 2);
+    assert_eq!(next.total, Some(5));
+
+    client
+        .channels()
+        .followers("141981764")
+        .user("4")
+        .build_iterable()
+        .await?;
+
+    let requests = server.requests();
+    assert!(requests.iter().all(|r| r.path == "/channels/followers"));
+    assert!(requests
+        .iter()
+        .all(|r| r.param("broadcaster_id") == Some("141981764")));
+    assert_eq!(requests[1].param("after"), Some("3"));
+    assert_eq!(requests[2].param("user_id"), Some("4"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_followed_channels() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let followed = client
+        .channels()
+        .followed("42")
+        .broadcaster("1")
+        .build_iterable()
+        .await?;
+    assert_eq!(followed.total, Some(3));
+    assert_eq!(followed.data[1].broadcaster_login, "user1");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/channels/followed");
+    assert_eq!(request.param("user_id"), Some("42"));
+    assert_eq!(request.param("broadcaster_id"), Some("1"));
+    Ok(())
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{game, paginate, StubServer};
+use hyper::StatusCode;
+use serde_json::json;
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::HelixClient;
+
+async fn setup() -> (StubServer, HelixClient) {
+    let games: Vec<_> = (0..5).map(game).collect();
+    let server = StubServer::start(move |req| match req.path.as_str() {
+        "/games/top" => (StatusCode::OK, paginate(&games, req)),
+        _ => (StatusCode::OK, json!({ "data": [game(1)] })),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_get_games() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let games = client
+        .games()
+        .by_names(&["Science & Technology", "Just Chatting"])
+        .await?;
+    assert_eq!(games.data[0].id, *"1");
+    assert_eq!(games.data[0].igdb_id, "1001");
+    assert_eq!(
+        games.data[0].box_art(52, 72),
+        "https://static-cdn.jtvnw.net/ttv-boxart/1-52x72.jpg"
+    );
+
+    client.games().by_ids(&["1"]).await?;
+    client.games().by_igdb_ids(&[1001]).await?;
+
+    let requests = server.requests();
+    assert!(requests.iter().all(|r| r.path == "/games"));
+    assert_eq!(
+        requests[0].query,
+        [
+            ("name".to_owned(), "Science & Technology".to_owned()),
+            ("name".to_owned(), "Just Chatting".to_owned()),
+        ]
+    );
+    assert_eq!(requests[1].param("id"), Some("1"));
+    assert_eq!(requests[2].param("igdb_id"), Some("1001"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_top_games() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client.games().top().first(2).build_iterable().await?;
+    assert_eq!(first.data.len(), 2);
+
+    let next = first.next().unwrap().await?;
+    assert_eq!(next.data[0].id, *"2");
+
+    let cursor = next.pagination.as_ref().unwrap().cursor.clone().unwrap();
+    let last = client.games().top().first(2).after(cursor).await?;
+    assert_eq!(last.data.len(), 1);
+    assert!(last.next().is_none());
+
+    let before = client.games().top().first(2).before("4").await?;
+    assert_eq!(before.data[0].id, *"2");
+
+    let requests = server.requests();
+    assert!(requests.iter().all(|r| r.path == "/games/top"));
+    assert_eq!(requests[2].param("after"), Some("4"));
+    assert_eq!(requests[3].param("before"), Some("4"));
+    Ok(())
+}
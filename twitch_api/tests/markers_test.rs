@@ -0,0 +1,164 @@
+mod common;
+
+use std::error::Error;
+use std::time::Duration;
+
+use common::stub_server::{paginate, stream, StubServer};
+use hyper::{Method, StatusCode};
+use serde_json::{json, Value};
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::error::ValidationError;
+use twitch_api::helix::cache::CacheConfig;
+use twitch_api::HelixClient;
+
+fn marker(id: usize) -> Value {
+    json!({
+        "user_id": "141981764",
+        "user_name": "TwitchDev",
+        "user_login": "twitchdev",
+        "videos": [{
+            "video_id": "456",
+            "markers": [{
+                "id": id.to_string(),
+                "created_at": "2018-08-20T20:10:03Z",
+                "description": "",
+                "position_seconds": 60 * id,
+                "URL": format!("https://twitch.tv/videos/456?t=0h{}m00s", id)
+            }]
+        }]
+    })
+}
+
+async fn setup() -> (StubServer, HelixClient) {
+    let markers: Vec<_> = (0..4).map(marker).collect();
+    let server = StubServer::start(move |req| match (&req.method, req.path.as_str()) {
+        (&Method::GET, "/streams") if req.param("user_id") == Some("141981764") => (
+            StatusCode::OK,
+            json!({ "data": [stream(1)], "pagination": {} }),
+        ),
+        (&Method::GET, "/streams") => (StatusCode::OK, json!({ "data": [], "pagination": {} })),
+        (&Method::POST, _) => (
+            StatusCode::OK,
+            json!({
+                "data": [{
+                    "id": "123",
+                    "created_at": "2018-08-20T20:10:03Z",
+                    "description": "hello, this is a marker!",
+                    "position_seconds": 244
+                }]
+            }),
+        ),
+        _ => (StatusCode::OK, paginate(&markers, req)),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    (server, client.as_user("valid"))
+}
+
+#[tokio::test]
+async fn test_create_marker() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let created = client
+        .streams()
+        .create_marker("141981764")
+        .description("hello, this is a marker!")
+        .await?;
+    assert_eq!(created.data[0].position(), Duration::from_secs(244));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].param("user_id"), Some("141981764"));
+    assert_eq!(requests[1].method, Method::POST);
+    assert_eq!(requests[1].path, "/streams/markers");
+    assert_eq!(
+        requests[1].body,
+        Some(json!({ "user_id": "141981764", "description": "hello, this is a marker!" }))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_marker_validation() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let offline = client.streams().create_marker("42").await.unwrap_err();
+    assert_eq!(
+        offline.get_validation_error(),
+        Some(&ValidationError::NotLive)
+    );
+
+    let description = "a".repeat(141);
+    let marker = client
+        .streams()
+        .create_marker("141981764")
+        .description(description);
+    assert_eq!(
+        marker.validate(),
+        Err(ValidationError::DescriptionTooLong(141))
+    );
+    assert!(marker.await.unwrap_err().is_validation_error());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/streams");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_markers_by_video() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client
+        .streams()
+        .markers_by_video("456")
+        .first(3)
+        .build_iterable()
+        .await?;
+    assert_eq!(first.data.len(), 3);
+    let marker = &first.data[2].videos[0].markers[0];
+    assert_eq!(marker.position(), Duration::from_secs(120));
+    assert_eq!(
+        marker.url.as_str(),
+        "https://twitch.tv/videos/456?t=0h2m00s"
+    );
+
+    let second = first.next().unwrap().await?;
+    assert_eq!(second.data.len(), 1);
+    assert!(second.next().is_none());
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.path, "/streams/markers");
+    assert_eq!(request.param("video_id"), Some("456"));
+    assert_eq!(request.param("after"), Some("3"));
+    assert_eq!(request.authorization.as_deref(), Some("Bearer valid"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_live_check_skips_cache() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|req| match req.method {
+        Method::POST => (StatusCode::OK, json!({ "data": [] })),
+        _ => (
+            StatusCode::OK,
+            json!({ "data": [stream(1)], "pagination": {} }),
+        ),
+    })
+    .await;
+    let config = common::stub_builder(&server.base_uri)
+        .cache(CacheConfig::in_memory(16))
+        .build()?;
+    let client = HelixClient::new_with_config("id", config).as_user("valid");
+
+    let streams = || client.streams().get().with_query("user_id", "141981764");
+    streams().await?;
+    streams().await?;
+    assert_eq!(server.requests().len(), 1);
+
+    /* The stream may have ended since the cached lookup */
+    client.streams().create_marker("141981764").await?;
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[1].path, "/streams");
+    assert_eq!(requests[2].method, Method::POST);
+    Ok(())
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{paginate, stream, StubServer};
+use hyper::{Method, StatusCode};
+use twitch_api::client::{BidirectionalPagination, PaginationCheckpoint};
+use twitch_api::helix::models::{PaginationContainer, Stream};
+use twitch_api::HelixClient;
+
+async fn setup() -> (StubServer, HelixClient) {
+    let items: Vec<_> = (0..10).map(stream).collect();
+    let server = StubServer::start(move |req| (StatusCode::OK, paginate(&items, req))).await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    (server, client)
+}
+
+fn ids<T: AsRef<str>>(ids: impl Iterator<Item = T>) -> Vec<String> {
+    ids.map(|id| id.as_ref().to_owned()).collect()
+}
+
+#[tokio::test]
+async fn test_forward_then_backward() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client.streams().get().first(3).build_iterable().await?;
+    assert!(first.prev().is_none());
+
+    let second = first.next().unwrap().await?;
+    let third = second.next().unwrap().await?;
+    assert_eq!(ids(third.data.iter().map(|s| &s.id)), ["6", "7", "8"]);
+
+    let back = third.prev().unwrap().await?;
+    assert_eq!(
+        ids(back.data.iter().map(|s| &s.id)),
+        ids(second.data.iter().map(|s| &s.id))
+    );
+
+    let back = back.prev().unwrap().await?;
+    assert_eq!(
+        ids(back.data.iter().map(|s| &s.id)),
+        ids(first.data.iter().map(|s| &s.id))
+    );
+    assert!(back.prev().is_none());
+
+    let requests = server.requests();
+    assert!(requests
+        .iter()
+        .all(|r| r.method == Method::GET && r.path == "/streams"));
+    let cursors: Vec<_> = requests
+        .iter()
+        .map(|r| (r.param("after"), r.param("before")))
+        .collect();
+    assert_eq!(
+        cursors,
+        [
+            (None, None),
+            (Some("3"), None),
+            (Some("6"), None),
+            (None, Some("6")),
+            (None, Some("3")),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_backward_then_forward() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client.streams().get().first(4).build_iterable().await?;
+    let second = first.next().unwrap().await?;
+    let last = second.next().unwrap().await?;
+    assert_eq!(ids(last.data.iter().map(|s| &s.id)), ["8", "9"]);
+    assert!(last.next().is_none());
+
+    let back = last.prev().unwrap().await?;
+    let forward = back.next().unwrap().await?;
+    assert_eq!(
+        ids(forward.data.iter().map(|s| &s.id)),
+        ids(last.data.iter().map(|s| &s.id))
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests[3].param("before"), Some("8"));
+    assert_eq!(requests[4].param("after"), Some("8"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_from_checkpoint() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client.streams().get().first(4).build_iterable().await?;
+    let checkpoint = first.checkpoint().unwrap();
+    assert_eq!(checkpoint.endpoint(), "/streams");
+    assert_eq!(checkpoint.cursor(), Some("4"));
+
+    let saved = serde_json::to_string(&checkpoint)?;
+    let checkpoint: PaginationCheckpoint = serde_json::from_str(&saved)?;
+
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    let mut request = Some(client.resume::<PaginationContainer<Stream>>(checkpoint));
+    let mut seen = ids(first.data.iter().map(|s| &s.id));
+    while let Some(r) = request {
+        let page = r.await?;
+        seen.extend(ids(page.data.iter().map(|s| &s.id)));
+        request = page.next();
+    }
+    assert_eq!(seen, ids((0..10).map(|i| i.to_string())));
+
+    let requests = server.requests();
+    assert_eq!(requests[1].param("after"), Some("4"));
+    assert_eq!(requests[1].param("first"), Some("4"));
+    assert_eq!(
+        first.next().unwrap().checkpoint(),
+        first.checkpoint().unwrap()
+    );
+    Ok(())
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{oauth, StubServer};
+use hyper::Method;
+use serde_json::json;
+use twitch_api::client::{curl_command, Client};
+use twitch_api::HelixClient;
+
+#[tokio::test]
+async fn test_prepare_does_not_send() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(oauth).await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri))
+        .authenticate("secret")
+        .token("valid")
+        .build()
+        .await?;
+    let sent = server.requests().len();
+
+    let request = client.streams().get().first(5).prepare(false)?;
+    assert_eq!(request.method(), Method::GET);
+    assert_eq!(
+        request.uri().to_string(),
+        server.base_uri.clone() + "/streams?first=5"
+    );
+    assert_eq!(request.headers()["Client-Id"], "id");
+    assert_eq!(request.headers()["Authorization"], "Bearer valid");
+    assert!(request.body().is_empty());
+
+    let request = client.streams().get().prepare(true)?;
+    assert_eq!(request.headers()["Authorization"], "Bearer REDACTED");
+
+    assert_eq!(server.requests().len(), sent);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prepare_redacts_secrets_in_query() -> Result<(), Box<dyn Error>> {
+    let client = Client::new("id", common::stub_config("http://localhost:1"));
+
+    let request = client.auth().client_credentials("secret").prepare(true)?;
+    let uri = request.uri().to_string();
+    assert!(uri.contains("client_secret=REDACTED"));
+    assert!(!uri.contains("secret&"));
+    assert!(uri.contains("client_id=id"));
+
+    let request = client.auth().client_credentials("secret").prepare(false)?;
+    assert!(request.uri().to_string().contains("client_secret=secret"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_curl_command() -> Result<(), Box<dyn Error>> {
+    let client = HelixClient::new_with_config("id", common::stub_config("http://localhost:1"));
+
+    let request = client
+        .raw(Method::POST, "/streams/markers")
+        .json(&json!({ "description": "it's live" }))?
+        .as_user("user_token")
+        .prepare(true)?;
+
+    assert_eq!(
+        curl_command(&request),
+        "curl -X POST 'http://localhost:1/streams/markers' \
+         -H 'client-id: id' \
+         -H 'authorization: Bearer REDACTED' \
+         -H 'content-type: application/json' \
+         --data '{\"description\":\"it'\\''s live\"}'"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_request_is_an_error() {
+    let client = HelixClient::new_with_config("id", common::stub_config("http://localhost:1"));
+
+    let invalid = || client.streams().get().as_user("token\n");
+    assert!(invalid().prepare(false).is_err());
+    assert!(invalid().await.is_err());
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use common::stub_server::{oauth, paginate, StubServer};
+use hyper::{Method, StatusCode};
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::HelixClient;
+
+#[derive(Debug, Deserialize)]
+struct Schedule {
+    data: Segments,
+}
+
+#[derive(Debug, Deserialize)]
+struct Segments {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Segment {
+    id: String,
+    title: String,
+}
+
+#[tokio::test]
+async fn test_raw_get() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|req| match req.path.as_str() {
+        "/schedule" => (
+            StatusCode::OK,
+            json!({ "data": { "segments": [{ "id": "1", "title": "Science & Tech" }] } }),
+        ),
+        _ => oauth(req),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri))
+        .authenticate("secret")
+        .build()
+        .await?;
+
+    let value: Value = client
+        .raw(Method::GET, "schedule")
+        .query("broadcaster_id", "141981764")
+        .query_all("id", &["a b", "c&d"])
+        .await?;
+    assert_eq!(value["data"]["segments"][0]["id"], "1");
+
+    let schedule = client
+        .raw(Method::GET, "/schedule")
+        .send::<Schedule>()
+        .await?;
+    assert_eq!(schedule.data.segments[0].id, "1");
+    assert_eq!(schedule.data.segments[0].title, "Science & Tech");
+
+    let request = server.requests().into_iter().nth(1).unwrap();
+    assert_eq!(request.authorization.as_deref(), Some("Bearer app_token"));
+    assert_eq!(
+        request.query,
+        [
+            ("broadcaster_id".to_owned(), "141981764".to_owned()),
+            ("id".to_owned(), "a b".to_owned()),
+            ("id".to_owned(), "c&d".to_owned()),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_raw_post_is_retried_with_body() -> Result<(), Box<dyn Error>> {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let server = StubServer::start(move |_| {
+        if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": "Internal Server Error", "status": 500, "message": "" }),
+            );
+        }
+        (StatusCode::OK, json!({ "data": [{ "id": "marker" }] }))
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let response: Value = client
+        .raw(Method::POST, "/streams/markers")
+        .json(&json!({ "user_id": "1", "description": "hello" }))?
+        .as_user("valid")
+        .await?;
+    assert_eq!(response["data"][0]["id"], "marker");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    for request in requests {
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.authorization.as_deref(), Some("Bearer valid"));
+        assert_eq!(
+            request.body,
+            Some(json!({ "user_id": "1", "description": "hello" }))
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_raw_paginate() -> Result<(), Box<dyn Error>> {
+    let items: Vec<_> = (0..5).map(|id| json!({ "id": id.to_string() })).collect();
+    let server = StubServer::start(move |req| (StatusCode::OK, paginate(&items, req))).await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let first = client
+        .raw(Method::GET, "/channels/followers")
+        .query("first", 3)
+        .paginate::<Value>()
+        .await?;
+    assert_eq!(first.data.len(), 3);
+
+    let second = first.next().unwrap().await?;
+    assert_eq!(second.data, [json!({ "id": "3" }), json!({ "id": "4" })]);
+    assert!(second.next().is_none());
+
+    let last = server.requests().pop().unwrap();
+    assert_eq!(last.path, "/channels/followers");
+    assert_eq!(last.param("after"), Some("3"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_raw_path_is_encoded() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|_| (StatusCode::OK, json!({ "data": [] }))).await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let request = client.raw(Method::GET, "/a b/c\nd?e").prepare(false)?;
+    assert_eq!(
+        request.uri().to_string(),
+        server.base_uri.clone() + "/a%20b/c%0Ad%3Fe"
+    );
+
+    client.raw(Method::GET, "/a b").await?;
+    assert_eq!(server.requests()[0].path, "/a%20b");
+    Ok(())
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{game, paginate, StubServer};
+use hyper::StatusCode;
+use serde_json::{json, Value};
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::HelixClient;
+
+fn channel(id: usize) -> Value {
+    json!({
+        "broadcaster_language": "en",
+        "broadcaster_login": format!("user{}", id),
+        "display_name": format!("User{}", id),
+        "game_id": "509670",
+        "game_name": "Science & Technology",
+        "id": id.to_string(),
+        "is_live": id % 2 == 0,
+        "tags": ["English"],
+        "thumbnail_url": "https://static-cdn.jtvnw.net/jtv_user_pictures/profile_image-300x300.png",
+        "title": "Stream",
+        "started_at": if id % 2 == 0 { "2021-03-10T15:04:21Z" } else { "" }
+    })
+}
+
+async fn setup() -> (StubServer, HelixClient) {
+    let games: Vec<_> = (0..3).map(game).collect();
+    let channels: Vec<_> = (0..4).map(channel).collect();
+    let server = StubServer::start(move |req| match req.path.as_str() {
+        "/search/categories" => (StatusCode::OK, paginate(&games, req)),
+        _ => (StatusCode::OK, paginate(&channels, req)),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_search_categories() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client
+        .search()
+        .categories("fort & night")
+        .first(2)
+        .build_iterable()
+        .await?;
+    assert_eq!(first.data[1].name, "Game 1");
+    let next = first.next().unwrap().await?;
+    assert_eq!(next.data.len(), 1);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/search/categories");
+    assert_eq!(requests[0].param("query"), Some("fort & night"));
+    assert_eq!(requests[1].param("query"), Some("fort & night"));
+    assert_eq!(requests[1].param("after"), Some("2"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_channels() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let channels = client
+        .search()
+        .channels("science")
+        .live_only(true)
+        .build_iterable()
+        .await?;
+    assert_eq!(channels.data.len(), 4);
+    assert!(channels.data[0].is_live);
+    assert!(channels.data[0].started_at.is_some());
+    assert_eq!(channels.data[1].started_at, None);
+    assert_eq!(channels.data[1].game_id, *"509670");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/search/channels");
+    assert_eq!(request.param("live_only"), Some("true"));
+    Ok(())
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use std::error::Error;
+
+use common::stub_server::{paginate, StubServer};
+use hyper::StatusCode;
+use serde_json::{json, Value};
+use twitch_api::client::BidirectionalPagination;
+use twitch_api::helix::models::SubscriptionTier;
+use twitch_api::HelixClient;
+
+fn subscription(id: usize) -> Value {
+    let gifted = id == 1;
+    let tier = ["1000", "2000", "3000"][id % 3];
+    json!({
+        "broadcaster_id": "141981764",
+        "broadcaster_login": "twitchdev",
+        "broadcaster_name": "TwitchDev",
+        "gifter_id": if gifted { "12826" } else { "" },
+        "gifter_login": if gifted { "twitch" } else { "" },
+        "gifter_name": if gifted { "Twitch" } else { "" },
+        "is_gift": gifted,
+        "tier": tier,
+        "plan_name": "Channel Subscription (twitchdev)",
+        "user_id": id.to_string(),
+        "user_name": format!("User{}", id),
+        "user_login": format!("user{}", id)
+    })
+}
+
+async fn setup() -> (StubServer, HelixClient) {
+    let subscriptions: Vec<_> = (0..4).map(subscription).collect();
+    let server = StubServer::start(move |req| match req.path.as_str() {
+        "/subscriptions" => {
+            let mut page = paginate(&subscriptions, req);
+            page["total"] = json!(13);
+            page["points"] = json!(13);
+            (StatusCode::OK, page)
+        }
+        _ if req.param("user_id") == Some("1") => {
+            let mut subscription = subscription(1);
+            subscription.as_object_mut().unwrap().remove("plan_name");
+            (StatusCode::OK, json!({ "data": [subscription] }))
+        }
+        _ => (
+            StatusCode::NOT_FOUND,
+            json!({
+                "error": "Not Found",
+                "status": 404,
+                "message": "twitchdev has no subscription to twitch"
+            }),
+        ),
+    })
+    .await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_broadcaster_subscriptions() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let first = client
+        .subscriptions()
+        .broadcaster("141981764")
+        .first(3)
+        .build_iterable()
+        .await?;
+    assert_eq!(first.total, 13);
+    assert_eq!(first.points, 13);
+
+    let tiers: Vec<_> = first.data.iter().map(|s| s.tier).collect();
+    assert_eq!(
+        tiers,
+        [
+            SubscriptionTier::Tier1,
+            SubscriptionTier::Tier2,
+            SubscriptionTier::Tier3
+        ]
+    );
+    assert_eq!(first.data[0].gifter_id, None);
+    assert!(first.data[1].is_gift);
+    assert_eq!(first.data[1].gifter_id.as_ref().unwrap(), "12826");
+    assert_eq!(first.data[1].gifter_name.as_deref(), Some("Twitch"));
+
+    let next = first.next().unwrap().await?;
+    assert_eq!(next.data.len(), 1);
+
+    client
+        .subscriptions()
+        .broadcaster("141981764")
+        .users(&["1", "2"])
+        .build_iterable()
+        .await?;
+    let request = server.requests().pop().unwrap();
+    let users: Vec<_> = request
+        .query
+        .iter()
+        .filter(|(k, _)| k == "user_id")
+        .collect();
+    assert_eq!(users.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_check_user_subscription() -> Result<(), Box<dyn Error>> {
+    let (server, client) = setup().await;
+
+    let subscription = client
+        .subscriptions()
+        .check("141981764", "1")
+        .as_user("user_token")
+        .await?
+        .unwrap();
+    assert_eq!(subscription.tier, SubscriptionTier::Tier2);
+    assert!(subscription.is_gift);
+    assert_eq!(subscription.gifter_login.as_deref(), Some("twitch"));
+
+    let subscription = client.subscriptions().check("141981764", "2").await?;
+    assert!(subscription.is_none());
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/subscriptions/user");
+    assert_eq!(request.param("broadcaster_id"), Some("141981764"));
+    assert_eq!(request.authorization.as_deref(), Some("Bearer user_token"));
+    Ok(())
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use std::error::Error;
+use std::future::IntoFuture;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use common::stub_server::{oauth, StubServer};
+use twitch_api::client::HelixScope;
+use twitch_api::helix::models::Credentials;
+use twitch_api::helix::tokens::{TokenEvent, TokenManager};
+use twitch_api::HelixClient;
+use twitch_types::UserId;
+
+fn credentials(token: &str, refresh_token: &str, expires_in: u32) -> Credentials {
+    let mut credentials = Credentials::new(token, Some(refresh_token.to_owned()));
+    credentials.expires_in = expires_in;
+    credentials
+}
+
+async fn setup() -> (StubServer, TokenManager) {
+    let server = StubServer::start(oauth).await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    let manager = TokenManager::new(&client, "secret");
+    (server, manager)
+}
+
+#[tokio::test]
+async fn test_add_validates_token() -> Result<(), Box<dyn Error>> {
+    let (server, manager) = setup().await;
+
+    let user = manager.add(credentials("valid", "good", 0)).await?;
+    assert_eq!(&user, "42");
+    assert!(manager
+        .add(credentials("unknown", "good", 0))
+        .await
+        .is_err());
+
+    let client = manager.client(&user).await?;
+    assert_eq!(client.scopes(), &[HelixScope::UserReadEmail]);
+    client.users().users(&["42"], &[] as &[&str]).await?;
+
+    let last = server.requests().pop().unwrap();
+    assert_eq!(last.path, "/users");
+    assert_eq!(last.authorization.as_deref(), Some("Bearer valid"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_expiring_token_is_refreshed() -> Result<(), Box<dyn Error>> {
+    let (server, manager) = setup().await;
+    let mut events = manager.subscribe();
+    let user = UserId::from("42");
+    manager
+        .insert(user.clone(), credentials("valid", "good", 10))
+        .await?;
+
+    let client = manager.client(&user).await?;
+    client.users().users(&["42"], &[] as &[&str]).await?;
+    assert_eq!(events.recv().await?, TokenEvent::Refreshed(user.clone()));
+
+    /* The refreshed token is valid for an hour and is used as is */
+    manager.client(&user).await?;
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].path, "/token");
+    assert_eq!(requests[0].param("grant_type"), Some("refresh_token"));
+    assert_eq!(
+        requests[1].authorization.as_deref(),
+        Some("Bearer refreshed")
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revoked_tokens_are_removed() -> Result<(), Box<dyn Error>> {
+    let (_server, manager) = setup().await;
+    let mut events = manager.subscribe();
+    let expired = UserId::from("1");
+    let revoked = UserId::from("2");
+    manager
+        .insert(expired.clone(), credentials("expired", "good", 0))
+        .await?;
+    manager
+        .insert(revoked.clone(), credentials("revoked", "bad", 0))
+        .await?;
+
+    assert_eq!(manager.validate().await, vec![revoked.clone()]);
+    assert_eq!(manager.users(), vec![expired.clone()]);
+    let received = vec![events.recv().await?, events.recv().await?];
+    assert!(received.contains(&TokenEvent::Refreshed(expired)));
+    assert!(received.contains(&TokenEvent::Revoked(revoked.clone())));
+
+    let err = manager.client(&revoked).await.unwrap_err();
+    assert!(err.is_auth_error());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_refreshed_token_shares_ratelimit() -> Result<(), Box<dyn Error>> {
+    /* Every response exhausts the bucket it was counted against */
+    let reset = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 60;
+    let headers = vec![
+        ("ratelimit-limit", "800".to_owned()),
+        ("ratelimit-remaining", "0".to_owned()),
+        ("ratelimit-reset", reset.to_string()),
+    ];
+    let server = StubServer::start_with_headers(headers, oauth).await;
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+    let manager = TokenManager::new(&client, "secret");
+    let user = UserId::from("42");
+    manager
+        .insert(user.clone(), credentials("valid", "good", 3600))
+        .await?;
+
+    let client = manager.client(&user).await?;
+    client.users().users(&["42"], &[] as &[&str]).await?;
+    manager.refresh(&user).await?;
+
+    /* Twitch limits the user, not the token */
+    let client = manager.client(&user).await?;
+    let lookup = client.users().users(&["42"], &[] as &[&str]).into_future();
+    assert!(tokio::time::timeout(Duration::from_millis(300), lookup)
+        .await
+        .is_err());
+    Ok(())
+}
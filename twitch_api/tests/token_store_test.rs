@@ -0,0 +1,117 @@
+mod common;
+
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common::stub_server::{oauth, StubServer};
+use twitch_api::helix::models::Credentials;
+use twitch_api::helix::token_store::{JsonFileStore, TokenStore};
+use twitch_api::helix::tokens::TokenManager;
+use twitch_api::HelixClient;
+use twitch_types::UserId;
+
+fn store_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{}.json", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn credentials(token: &str, refresh_token: &str) -> Credentials {
+    Credentials::new(token, Some(refresh_token.to_owned()))
+}
+
+#[tokio::test]
+async fn test_client_token_is_reused() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(oauth).await;
+    let path = store_path("twitch_api_client_token");
+    let store: Arc<dyn TokenStore> = Arc::new(JsonFileStore::new(&path));
+    let build = || {
+        HelixClient::new_with_config("id", common::stub_config(&server.base_uri))
+            .authenticate("secret")
+            .token_store(store.clone(), "app")
+            .build()
+    };
+
+    build().await?;
+    assert_eq!(store.load("app").await?.unwrap().access_token, "app_token");
+
+    /* A restarted client validates the stored token instead of requesting
+     * a new one */
+    build().await?;
+    let paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(paths, vec!["/token", "/validate"]);
+
+    /* Expired tokens are refreshed and written back */
+    store.save("app", &credentials("expired", "good")).await?;
+    build().await?;
+    assert_eq!(store.load("app").await?.unwrap().access_token, "refreshed");
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejected_stored_token_is_an_error() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(oauth).await;
+    let path = store_path("twitch_api_rejected_token");
+    let store: Arc<dyn TokenStore> = Arc::new(JsonFileStore::new(&path));
+    let build = || {
+        HelixClient::new_with_config("id", common::stub_config(&server.base_uri))
+            .authenticate("secret")
+            .token_store(store.clone(), "user")
+            .build()
+    };
+
+    /* A rejected refresh and a missing refresh token are handled alike */
+    for stored in &[
+        credentials("expired", "bad"),
+        Credentials::new("expired", None),
+    ] {
+        store.save("user", stored).await?;
+        let err = build().await.unwrap_err();
+        assert!(err.is_stored_token_rejected());
+        assert!(store.load("user").await?.is_none());
+    }
+
+    /* Without a stored token the client starts over */
+    build().await?;
+    assert_eq!(store.load("user").await?.unwrap().access_token, "app_token");
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_manager_writes_back_tokens() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(oauth).await;
+    let path = store_path("twitch_api_user_tokens");
+    let store: Arc<dyn TokenStore> = Arc::new(JsonFileStore::new(&path));
+    let client = HelixClient::new_with_config("id", common::stub_config(&server.base_uri));
+
+    let manager = TokenManager::new_with_store(&client, "secret", store.clone());
+    let refreshed = UserId::from("1");
+    let revoked = UserId::from("2");
+    manager
+        .insert(refreshed.clone(), credentials("expired", "good"))
+        .await?;
+    manager
+        .insert(revoked.clone(), credentials("expired", "bad"))
+        .await?;
+    store.save("app", &credentials("valid", "good")).await?;
+
+    /* After a restart only the usable token is left, in its refreshed form */
+    let manager = TokenManager::new_with_store(&client, "secret", store.clone());
+    assert_eq!(manager.restore().await?, vec![refreshed.clone()]);
+    assert_eq!(store.keys().await?, vec!["app", "user:1"]);
+    assert_eq!(
+        store.load("user:1").await?.unwrap().access_token,
+        "refreshed"
+    );
+
+    assert!(manager.remove(&refreshed).await?);
+    assert_eq!(store.keys().await?, vec!["app"]);
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
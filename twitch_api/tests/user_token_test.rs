@@ -0,0 +1,113 @@
+mod common;
+
+use std::error::Error;
+use std::future::IntoFuture;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use common::stub_server::{user, StubServer};
+use hyper::StatusCode;
+use serde_json::json;
+use twitch_api::helix::cache::CacheConfig;
+use twitch_api::HelixClient;
+
+async fn app_client(server: &StubServer) -> Result<HelixClient, Box<dyn Error>> {
+    let config = common::stub_builder(&server.base_uri)
+        .cache(CacheConfig::in_memory(16))
+        .build()?;
+    let client = HelixClient::new_with_config("id", config)
+        .authenticate("secret")
+        .token("app_token")
+        .build()
+        .await?;
+    Ok(client)
+}
+
+#[tokio::test]
+async fn test_request_as_user() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|_| (StatusCode::OK, json!({ "data": [user("1")] }))).await;
+    let client = app_client(&server).await?;
+
+    client.users().users(&["1"], &[] as &[&str]).await?;
+    client
+        .users()
+        .users(&["1"], &[] as &[&str])
+        .as_user("user_token")
+        .await?;
+    client
+        .as_user("other_token")
+        .users()
+        .users(&["1"], &[] as &[&str])
+        .await?;
+
+    /* Cached responses are not shared between tokens */
+    let tokens: Vec<_> = server
+        .requests()
+        .into_iter()
+        .map(|r| r.authorization.unwrap())
+        .collect();
+    assert_eq!(
+        tokens,
+        vec![
+            "Bearer app_token",
+            "Bearer user_token",
+            "Bearer other_token"
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tokens_have_separate_ratelimits() -> Result<(), Box<dyn Error>> {
+    /* Every response exhausts the bucket of the token until the next minute */
+    let reset = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 60;
+    let headers = vec![
+        ("ratelimit-limit", "800".to_owned()),
+        ("ratelimit-remaining", "0".to_owned()),
+        ("ratelimit-reset", reset.to_string()),
+    ];
+    let server = StubServer::start_with_headers(headers, |_| {
+        (StatusCode::OK, json!({ "data": [user("1")] }))
+    })
+    .await;
+    let client = app_client(&server).await?;
+    let users = |id: &str| client.users().users(&[id], &[] as &[&str]);
+
+    users("1").as_user("first").await?;
+    users("1").as_user("second").await?;
+    client
+        .as_user("third")
+        .users()
+        .users(&["2"], &[] as &[&str])
+        .await?;
+
+    let limited = users("4").as_user("first").into_future();
+    assert!(tokio::time::timeout(Duration::from_millis(200), limited)
+        .await
+        .is_err());
+    assert_eq!(server.requests().len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unauthorized_response_keeps_api_error() -> Result<(), Box<dyn Error>> {
+    let server = StubServer::start(|req| match req.authorization.as_deref() {
+        Some("Bearer expired") => (
+            StatusCode::UNAUTHORIZED,
+            json!({ "error": "Unauthorized", "status": 401, "message": "Invalid OAuth token" }),
+        ),
+        _ => (StatusCode::OK, json!({ "data": [user("1")] })),
+    })
+    .await;
+    let client = app_client(&server).await?;
+
+    let err = client
+        .users()
+        .users(&["1"], &[] as &[&str])
+        .as_user("expired")
+        .await
+        .unwrap_err();
+    let api_error = err.get_api_error().expect("api error");
+    assert_eq!(api_error.status, 401);
+    assert_eq!(api_error.message, "Invalid OAuth token");
+    Ok(())
+}
@@ -6,6 +6,7 @@ use std::convert::AsRef;
 use std::convert::Into;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
 /* Used for Id's that can be interpreted as integers but aren't returned as
@@ -29,7 +30,6 @@ pub type ClipId<'a> = StringId<'a, Clip>;
 pub type GameId<'a> = StringId<'a, Game>;
 pub type StreamId<'a> = StringId<'a, Stream>;
 
-#[derive(Clone)]
 pub struct StringId<'a, T> {
     id: Cow<'a, str>,
     marker: PhantomData<T>,
@@ -93,6 +93,19 @@ impl<'a, T> PartialEq<StringId<'a, T>> for StringId<'a, T> {
 }
 impl<'a, T> Eq for StringId<'a, T> {}
 
+/* Derived impls would require the marker type to implement the trait */
+impl<'a, T> Clone for StringId<'a, T> {
+    fn clone(&self) -> Self {
+        StringId::new(self.id.clone())
+    }
+}
+
+impl<'a, T> Hash for StringId<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
 impl<'a, T> PartialEq<str> for StringId<'a, T> {
     fn eq(&self, other: &str) -> bool {
         self.id.eq(other)